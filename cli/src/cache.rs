@@ -0,0 +1,910 @@
+//! Local cache of template repositories
+//!
+//! Remote templates are cloned once into a mirror under the XDG cache directory and each
+//! commit used is exported next to it:
+//!
+//! ```text
+//! ~/.cache/cza/templates/
+//! └── https___github_com_sripwoud_cza_templates-<hash>/
+//!     ├── cache.toml        # repository URL and last fetch time
+//!     ├── mirror.git/       # bare mirror of the repository
+//!     └── <commit>/         # files of the repository at <commit>
+//! ```
+//!
+//! Generating a project then copies the template from disk instead of cloning the
+//! repository every time. Online, mirrors are refreshed with `git fetch` before resolving
+//! a revision, unless it is a commit SHA that is already cached. Offline (`--offline`),
+//! revisions are resolved from the mirror as of its last fetch, and templates that were
+//! never cached can't be used. A mirror is only used for the repository recorded in its
+//! `cache.toml`.
+
+use crate::error::CzaError;
+use crate::template::{self, TemplateInfo};
+use anyhow::{anyhow, Context, Result};
+use cargo_generate::TemplatePath;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+use tempfile::TempDir;
+
+/// Name of the metadata file stored for each cached repository
+const METADATA_FILE_NAME: &str = "cache.toml";
+
+/// Name of the bare mirror directory of each cached repository
+const MIRROR_DIR_NAME: &str = "mirror.git";
+
+/// Metadata stored for each cached repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct RepositoryMetadata {
+    repository: String,
+    fetched_at: String,
+}
+
+/// A cached repository, as reported by `cza cache list`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CachedRepository {
+    /// Repository URL
+    pub repository: String,
+    /// RFC 3339 timestamp of the last clone or fetch
+    pub fetched_at: String,
+    /// Commits exported from the mirror
+    pub commits: Vec<String>,
+    /// Size on disk in bytes
+    pub size: u64,
+}
+
+/// A commit of a cached repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommitSummary {
+    pub commit: String,
+    /// Committer date (YYYY-MM-DD)
+    pub date: String,
+    /// First line of the commit message
+    pub subject: String,
+}
+
+/// Where cargo-generate should read a template from, and the commit it resolves to
+#[derive(Debug)]
+pub struct PreparedTemplate {
+    pub template_path: TemplatePath,
+    /// Commit of the files at `template_path`, `None` when unknown or when a local
+    /// template has uncommitted changes
+    pub commit: Option<String>,
+    /// Root of the template repository on disk at the resolved revision, where its
+    /// `cza.toml` manifest lives
+    pub repository_dir: PathBuf,
+    /// Temporary clone `template_path` points into, removed when dropped
+    pub scratch: Option<TempDir>,
+}
+
+/// On-disk cache of template repositories
+#[derive(Debug, Clone)]
+pub struct TemplateCache {
+    root: PathBuf,
+}
+
+impl TemplateCache {
+    /// Cache stored under the user cache directory (`~/.cache/cza/templates`)
+    pub fn open() -> Result<Self> {
+        let root = dirs::cache_dir()
+            .context("Could not find cache directory")?
+            .join("cza")
+            .join("templates");
+        Ok(Self::new(root))
+    }
+
+    /// Cache stored in `root`
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Directory holding the cache
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn repository_dir(&self, repository: &str) -> PathBuf {
+        self.root.join(cache_key(repository))
+    }
+
+    /// Whether the cached directory of `repository` holds a mirror of that repository
+    ///
+    /// Read from `cache.toml`, or from the mirror's remote when the metadata is missing.
+    fn holds(&self, repository: &str) -> bool {
+        let dir = self.repository_dir(repository);
+        let cached = match read_metadata(&dir) {
+            Some(metadata) => Some(metadata.repository),
+            None => git(
+                &dir.join(MIRROR_DIR_NAME),
+                &["config", "--get", "remote.origin.url"],
+            )
+            .ok(),
+        };
+        if cached.as_deref() == Some(repository) {
+            return true;
+        }
+        warn!(
+            "{} holds {}, not {}",
+            dir.display(),
+            cached.as_deref().unwrap_or("an unknown repository"),
+            repository
+        );
+        false
+    }
+
+    /// Fail unless the cached directory of `repository` holds a mirror of it
+    fn check_holds(&self, repository: &str) -> Result<()> {
+        if self.holds(repository) {
+            return Ok(());
+        }
+        Err(anyhow!(
+            "Template cache directory {} holds another repository than {}. Run 'cza cache clean' to reset the cache",
+            self.repository_dir(repository).display(),
+            repository
+        ))
+    }
+
+    fn mirror_dir(&self, repository: &str) -> PathBuf {
+        self.repository_dir(repository).join(MIRROR_DIR_NAME)
+    }
+
+    /// `template_path` pointing to the cached mirror at the commit `revision` resolves to
+    fn mirror_template_path(
+        &self,
+        template_info: &TemplateInfo,
+        revision: Option<&str>,
+        template_path: TemplatePath,
+    ) -> Result<TemplatePath, CzaError> {
+        let repository = &template_info.repository;
+        let commit = self
+            .resolve(repository, revision)
+            .ok_or_else(|| not_cached(template_info, revision))?;
+        Ok(TemplatePath {
+            git: Some(self.mirror_dir(repository).to_string_lossy().to_string()),
+            revision: Some(commit),
+            ..template_path
+        })
+    }
+
+    /// Whether a mirror of `repository` exists
+    pub fn contains(&self, repository: &str) -> bool {
+        self.mirror_dir(repository).is_dir() && self.holds(repository)
+    }
+
+    /// Clone `repository` into the cache, or fetch its latest refs if already cached
+    pub fn fetch(&self, repository: &str) -> Result<()> {
+        let mirror = self.mirror_dir(repository);
+        if mirror.is_dir() {
+            self.check_holds(repository)?;
+            debug!("Fetching {} into {}", repository, mirror.display());
+            git(
+                &mirror,
+                &["fetch", "--quiet", "--prune", "--tags", "origin"],
+            )
+            .with_context(|| format!("Failed to fetch {}", repository))?;
+        } else {
+            debug!("Cloning {} into {}", repository, mirror.display());
+            let repository_dir = self.repository_dir(repository);
+            fs::create_dir_all(&repository_dir)
+                .context("Failed to create template cache directory")?;
+            let mirror_arg = mirror.to_string_lossy();
+            git(
+                &repository_dir,
+                &["clone", "--quiet", "--mirror", repository, &mirror_arg],
+            )
+            .map_err(|e| CzaError::CloneFailed {
+                repository: repository.to_string(),
+                reason: format!("{:#}", e),
+            })?;
+        }
+
+        let metadata = RepositoryMetadata {
+            repository: repository.to_string(),
+            fetched_at: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+        };
+        let contents =
+            toml::to_string_pretty(&metadata).context("Failed to serialize cache metadata")?;
+        fs::write(
+            self.repository_dir(repository).join(METADATA_FILE_NAME),
+            contents,
+        )
+        .context("Failed to write cache metadata")
+    }
+
+    /// Resolve `revision` (latest when `None`) to a commit SHA using the cached mirror
+    pub fn resolve(&self, repository: &str, revision: Option<&str>) -> Option<String> {
+        if !self.contains(repository) {
+            return None;
+        }
+        let mirror = self.mirror_dir(repository);
+        let rev = format!("{}^{{commit}}", revision.unwrap_or("HEAD"));
+        git(&mirror, &["rev-parse", "--verify", "--quiet", &rev]).ok()
+    }
+
+    /// Date and subject of `commit` from the cached mirror of `repository`
+    pub fn describe(&self, repository: &str, commit: &str) -> Option<CommitSummary> {
+        if !self.contains(repository) {
+            return None;
+        }
+        let mirror = self.mirror_dir(repository);
+        let rev = format!("{}^{{commit}}", commit);
+        let log = git(&mirror, &["log", "-1", "--format=%H%n%cs%n%s", &rev]).ok()?;
+        let mut lines = log.lines();
+        Some(CommitSummary {
+            commit: lines.next()?.to_string(),
+            date: lines.next()?.to_string(),
+            subject: lines.next().unwrap_or_default().to_string(),
+        })
+    }
+
+    /// Directory holding the files of `repository` at `commit`, exported on first use
+    pub fn checkout(&self, repository: &str, commit: &str) -> Result<PathBuf> {
+        self.check_holds(repository)?;
+        let dir = self.repository_dir(repository).join(commit);
+        if dir.is_dir() {
+            debug!("Using cached checkout {}", dir.display());
+            return Ok(dir);
+        }
+
+        // Export into a staging directory first so an interrupted export is never used
+        let staging = self
+            .repository_dir(repository)
+            .join(format!("{}.tmp", commit));
+        if staging.exists() {
+            fs::remove_dir_all(&staging).context("Failed to remove stale checkout")?;
+        }
+        fs::create_dir_all(&staging).context("Failed to create checkout directory")?;
+
+        let mirror = self.mirror_dir(repository);
+        let index = self
+            .repository_dir(repository)
+            .join(format!("{}.index", commit));
+        let export = Command::new("git")
+            .arg("--git-dir")
+            .arg(&mirror)
+            .arg("--work-tree")
+            .arg(&staging)
+            .env("GIT_INDEX_FILE", &index)
+            .args(["read-tree", commit])
+            .status()
+            .and_then(|status| {
+                if !status.success() {
+                    return Ok(status);
+                }
+                Command::new("git")
+                    .arg("--git-dir")
+                    .arg(&mirror)
+                    .arg("--work-tree")
+                    .arg(&staging)
+                    .env("GIT_INDEX_FILE", &index)
+                    .args(["checkout-index", "--all", "--force"])
+                    .status()
+            });
+        let _ = fs::remove_file(&index);
+
+        match export {
+            Ok(status) if status.success() => {
+                fs::rename(&staging, &dir).context("Failed to store checkout")?;
+                debug!("Exported {} at {} to {}", repository, commit, dir.display());
+                Ok(dir)
+            }
+            result => {
+                let _ = fs::remove_dir_all(&staging);
+                Err(anyhow!(
+                    "Failed to check out {} at {}: {}",
+                    repository,
+                    commit,
+                    result.map_or_else(|e| e.to_string(), |status| status.to_string())
+                ))
+            }
+        }
+    }
+
+    /// Cached repositories, sorted by URL
+    pub fn list(&self) -> Result<Vec<CachedRepository>> {
+        if !self.root.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut repositories = Vec::new();
+        for entry in fs::read_dir(&self.root).context("Failed to read template cache")? {
+            let dir = entry?.path();
+            let Some(metadata) = read_metadata(&dir) else {
+                continue;
+            };
+
+            let mut commits: Vec<String> = fs::read_dir(&dir)?
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| template::is_full_sha(name))
+                .collect();
+            commits.sort();
+
+            repositories.push(CachedRepository {
+                repository: metadata.repository,
+                fetched_at: metadata.fetched_at,
+                commits,
+                size: dir_size(&dir),
+            });
+        }
+        repositories.sort_by(|a, b| a.repository.cmp(&b.repository));
+        Ok(repositories)
+    }
+
+    /// Check the cache for damage, returning a description of each problem found
+    ///
+    /// Detects missing or invalid metadata, mirrors git can't read, exports left behind by
+    /// an interrupted checkout and checkouts of commits missing from their mirror.
+    pub fn verify(&self) -> Result<Vec<String>> {
+        if !self.root.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut problems = Vec::new();
+        for entry in fs::read_dir(&self.root).context("Failed to read template cache")? {
+            let dir = entry?.path();
+            if !dir.is_dir() {
+                continue;
+            }
+            let label = dir
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+
+            if read_metadata(&dir).is_none() {
+                problems.push(format!(
+                    "{}: missing or invalid {}",
+                    label, METADATA_FILE_NAME
+                ));
+            }
+
+            let mirror = dir.join(MIRROR_DIR_NAME);
+            if !git(&mirror, &["rev-parse", "--is-bare-repository"])
+                .is_ok_and(|bare| bare == "true")
+            {
+                problems.push(format!(
+                    "{}: {} is not a readable git mirror",
+                    label, MIRROR_DIR_NAME
+                ));
+                continue;
+            }
+
+            for entry in fs::read_dir(&dir)?.filter_map(|entry| entry.ok()) {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.ends_with(".tmp") || name.ends_with(".index") {
+                    problems.push(format!(
+                        "{}: leftover {} from an interrupted checkout",
+                        label, name
+                    ));
+                } else if template::is_full_sha(&name) {
+                    let object = format!("{}^{{commit}}", name);
+                    if git(&mirror, &["cat-file", "-e", &object]).is_err() {
+                        problems.push(format!(
+                            "{}: checkout {} is not a commit of the mirror",
+                            label, name
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(problems)
+    }
+
+    /// Remove the whole cache, returning the number of bytes freed
+    pub fn clean(&self) -> Result<u64> {
+        if !self.root.exists() {
+            return Ok(0);
+        }
+        let size = dir_size(&self.root);
+        fs::remove_dir_all(&self.root)
+            .with_context(|| format!("Failed to remove {}", self.root.display()))?;
+        Ok(size)
+    }
+
+    /// Fetch `repository` if needed and return the checkout of `revision` with its commit
+    ///
+    /// Full commit SHAs that are already cached don't trigger a fetch.
+    pub fn warm(&self, repository: &str, revision: Option<&str>) -> Result<(PathBuf, String)> {
+        let cached_commit = revision
+            .filter(|rev| template::is_full_sha(rev))
+            .and_then(|rev| self.resolve(repository, Some(rev)));
+        let commit = match cached_commit {
+            Some(commit) => commit,
+            None => {
+                self.fetch(repository)?;
+                self.resolve(repository, revision).ok_or_else(|| {
+                    anyhow!(
+                        "Revision '{}' not found in {}",
+                        revision.unwrap_or("HEAD"),
+                        repository
+                    )
+                })?
+            }
+        };
+        let dir = self.checkout(repository, &commit)?;
+        Ok((dir, commit))
+    }
+}
+
+/// Prepare a template for generation, going through the cache for remote templates
+///
+/// Local templates are used in place, or cloned at `revision` into a temporary directory.
+/// Remote templates are fetched into the cache and generated from a cached checkout;
+/// when the cache can't be used online, they are cloned into a temporary directory
+/// instead. With `offline`, the template must already be cached. Either way the commit
+/// is the one of the files generated from.
+pub fn prepare(
+    template_info: &TemplateInfo,
+    revision: Option<&str>,
+    offline: bool,
+) -> Result<PreparedTemplate> {
+    if template_info.is_local() {
+        let template_path = template_info.template_path(revision.map(str::to_string))?;
+        let Some(ref dir) = template_path.path else {
+            // The working tree doesn't reflect other revisions
+            return clone_template(template_path);
+        };
+        let commit = if has_local_changes(Path::new(dir)) {
+            debug!("{} has uncommitted changes, not recording a commit", dir);
+            None
+        } else {
+            template::resolve_commit(template_info, None)
+        };
+        let repository_dir = template_info
+            .local_path()
+            .context("Local template has no path")?;
+        return Ok(PreparedTemplate {
+            template_path,
+            commit,
+            repository_dir,
+            scratch: None,
+        });
+    }
+
+    let cache = TemplateCache::open()?;
+    let repository = &template_info.repository;
+
+    let cached = if offline {
+        let commit = cache
+            .resolve(repository, revision)
+            .ok_or_else(|| not_cached(template_info, revision))?;
+        (cache.checkout(repository, &commit)?, commit)
+    } else {
+        match cache.warm(repository, revision) {
+            Ok(cached) => cached,
+            Err(e) => {
+                warn!("Template cache unavailable ({:#}), cloning directly", e);
+                return clone_template(template_info.template_path(revision.map(str::to_string))?);
+            }
+        }
+    };
+
+    let (root, commit) = cached;
+    let dir = if template_info.subfolder.is_empty() {
+        root.clone()
+    } else {
+        root.join(&template_info.subfolder)
+    };
+    if !dir.is_dir() {
+        return Err(anyhow!(
+            "Template subfolder '{}' does not exist in {} at {}",
+            template_info.subfolder,
+            repository,
+            commit
+        ));
+    }
+
+    debug!(
+        "Generating {} from cached checkout {}",
+        template_info.name,
+        dir.display()
+    );
+    Ok(PreparedTemplate {
+        template_path: TemplatePath {
+            path: Some(dir.to_string_lossy().to_string()),
+            ..Default::default()
+        },
+        commit: Some(commit),
+        repository_dir: root,
+        scratch: None,
+    })
+}
+
+/// Prepare a template like [`prepare`], without writing to the template cache
+///
+/// Remote templates are cloned into a temporary directory, from their cached mirror when
+/// `offline`. Used by previews that must not leave anything on disk.
+pub fn prepare_uncached(
+    template_info: &TemplateInfo,
+    revision: Option<&str>,
+    offline: bool,
+) -> Result<PreparedTemplate> {
+    if template_info.is_local() {
+        return prepare(template_info, revision, offline);
+    }
+
+    let template_path = template_info.template_path(revision.map(str::to_string))?;
+    if offline {
+        let cache = TemplateCache::open()?;
+        return clone_template(cache.mirror_template_path(
+            template_info,
+            revision,
+            template_path,
+        )?);
+    }
+    clone_template(template_path)
+}
+
+fn not_cached(template_info: &TemplateInfo, revision: Option<&str>) -> CzaError {
+    CzaError::TemplateNotCached {
+        template: template_info.name.clone(),
+        repository: template_info.repository.clone(),
+        revision: revision.unwrap_or("latest").to_string(),
+    }
+}
+
+/// Clone the git repository of `template_path` at its revision into a temporary
+/// directory, so the commit is read from the files generated from
+fn clone_template(template_path: TemplatePath) -> Result<PreparedTemplate> {
+    let repository = template_path
+        .git
+        .context("Template path has no git repository to clone")?;
+    let scratch = tempfile::Builder::new()
+        .prefix("cza-template-")
+        .tempdir()
+        .context("Failed to create temporary directory")?;
+    let root = scratch.path();
+
+    debug!("Cloning {} into {}", repository, root.display());
+    git(root, &["clone", "--quiet", &repository, "."]).map_err(|e| CzaError::CloneFailed {
+        repository: repository.clone(),
+        reason: format!("{:#}", e),
+    })?;
+    if let Some(ref revision) = template_path.revision {
+        git(root, &["checkout", "--quiet", "--detach", revision])
+            .with_context(|| format!("Revision '{}' not found in {}", revision, repository))?;
+    }
+    let commit = git(root, &["rev-parse", "HEAD"])?;
+
+    let dir = root.join(template_path.subfolder.unwrap_or_default());
+    if !dir.is_dir() {
+        return Err(anyhow!(
+            "Template subfolder '{}' does not exist in {} at {}",
+            dir.strip_prefix(root).unwrap_or(&dir).display(),
+            repository,
+            commit
+        ));
+    }
+    Ok(PreparedTemplate {
+        template_path: TemplatePath {
+            path: Some(dir.to_string_lossy().to_string()),
+            ..Default::default()
+        },
+        commit: Some(commit),
+        repository_dir: root.to_path_buf(),
+        scratch: Some(scratch),
+    })
+}
+
+/// Whether the files below `dir` differ from the commit checked out in its repository
+fn has_local_changes(dir: &Path) -> bool {
+    git(dir, &["status", "--porcelain", "--", "."]).is_ok_and(|status| !status.is_empty())
+}
+
+/// Run git in `dir` and return its trimmed stdout
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Metadata of the cached repository in `dir`, if present and valid
+fn read_metadata(dir: &Path) -> Option<RepositoryMetadata> {
+    let contents = fs::read_to_string(dir.join(METADATA_FILE_NAME)).ok()?;
+    let metadata = toml::from_str(&contents);
+    if metadata.is_err() {
+        warn!("Ignoring invalid cache metadata in {}", dir.display());
+    }
+    metadata.ok()
+}
+
+/// File name for a cached repository or registry location
+///
+/// The sanitized location keeps the name readable and the hash keeps it unique, as
+/// different locations can sanitize to the same string.
+pub(crate) fn cache_key(location: &str) -> String {
+    let sanitized: String = location
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{}-{:016x}", sanitized, fnv1a(location))
+}
+
+/// 64-bit FNV-1a hash, stable across Rust versions unlike `DefaultHasher`
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Total size of the files below `dir`
+pub fn dir_size(dir: &Path) -> u64 {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| match entry.file_type() {
+                    Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+                    Ok(_) => entry.metadata().map(|m| m.len()).unwrap_or(0),
+                    Err(_) => 0,
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Create a git repository with a template in `noir/` and return its path
+    fn template_repository(dir: &Path) -> Option<PathBuf> {
+        let repo = dir.join("templates");
+        fs::create_dir_all(repo.join("noir")).unwrap();
+        fs::write(repo.join("noir").join("README.md"), "v1\n").unwrap();
+
+        let run = |args: &[&str]| {
+            Command::new("git")
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(&repo)
+                .output()
+                .is_ok_and(|output| output.status.success())
+        };
+        if !run(&["init", "-q"]) {
+            return None;
+        }
+        run(&["add", "."]);
+        run(&["commit", "-qm", "v1"]);
+        run(&["tag", "v1"]);
+        fs::write(repo.join("noir").join("README.md"), "v2\n").unwrap();
+        run(&["commit", "-qam", "v2"]);
+        Some(repo)
+    }
+
+    #[test]
+    fn test_warm_and_resolve() {
+        let temp_dir = TempDir::new().unwrap();
+        let Some(repo) = template_repository(temp_dir.path()) else {
+            return;
+        };
+        let repository = repo.to_string_lossy().to_string();
+        let cache = TemplateCache::new(temp_dir.path().join("cache"));
+        assert!(!cache.contains(&repository));
+
+        let (latest_dir, latest) = cache.warm(&repository, None).unwrap();
+        assert!(cache.contains(&repository));
+        assert_eq!(
+            fs::read_to_string(latest_dir.join("noir").join("README.md")).unwrap(),
+            "v2\n"
+        );
+        assert!(!latest_dir.join(".git").exists());
+
+        let (v1_dir, v1) = cache.warm(&repository, Some("v1")).unwrap();
+        assert_ne!(v1, latest);
+        assert_eq!(
+            fs::read_to_string(v1_dir.join("noir").join("README.md")).unwrap(),
+            "v1\n"
+        );
+
+        // Revisions resolve from the mirror without network access
+        assert_eq!(cache.resolve(&repository, Some("v1")), Some(v1.clone()));
+        assert_eq!(cache.resolve(&repository, None), Some(latest));
+        assert_eq!(cache.resolve(&repository, Some("missing")), None);
+        assert!(cache.warm(&repository, Some("missing")).is_err());
+
+        let summary = cache.describe(&repository, "v1").unwrap();
+        assert_eq!(summary.commit, v1);
+        assert_eq!(summary.subject, "v1");
+        assert_eq!(summary.date.len(), "YYYY-MM-DD".len());
+
+        let listed = cache.list().unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].repository, repository);
+        assert_eq!(listed[0].commits.len(), 2);
+        assert!(listed[0].size > 0);
+    }
+
+    #[test]
+    fn test_verify() {
+        let temp_dir = TempDir::new().unwrap();
+        let Some(repo) = template_repository(temp_dir.path()) else {
+            return;
+        };
+        let repository = repo.to_string_lossy().to_string();
+        let cache = TemplateCache::new(temp_dir.path().join("cache"));
+        assert!(cache.verify().unwrap().is_empty());
+
+        cache.warm(&repository, None).unwrap();
+        assert!(cache.verify().unwrap().is_empty());
+
+        let repository_dir = cache.repository_dir(&repository);
+        fs::create_dir_all(repository_dir.join(format!("{}.tmp", "a".repeat(40)))).unwrap();
+        fs::create_dir_all(repository_dir.join("b".repeat(40))).unwrap();
+        fs::remove_file(repository_dir.join(METADATA_FILE_NAME)).unwrap();
+
+        let problems = cache.verify().unwrap();
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.contains("interrupted checkout")));
+        assert!(problems.iter().any(|p| p.contains("is not a commit")));
+        assert!(problems.iter().any(|p| p.contains("cache.toml")));
+
+        fs::remove_dir_all(repository_dir.join(MIRROR_DIR_NAME)).unwrap();
+        assert!(cache
+            .verify()
+            .unwrap()
+            .iter()
+            .any(|p| p.contains("not a readable git mirror")));
+    }
+
+    #[test]
+    fn test_clean() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = TemplateCache::new(temp_dir.path().join("cache"));
+        assert_eq!(cache.clean().unwrap(), 0);
+
+        fs::create_dir_all(cache.root().join("repo")).unwrap();
+        fs::write(cache.root().join("repo").join("file"), "12345").unwrap();
+        assert_eq!(cache.clean().unwrap(), 5);
+        assert!(!cache.root().exists());
+        assert!(cache.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_clone_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let Some(repo) = template_repository(temp_dir.path()) else {
+            return;
+        };
+        let v1 = git(&repo, &["rev-parse", "v1"]).unwrap();
+        let prepared = clone_template(TemplatePath {
+            git: Some(repo.to_string_lossy().to_string()),
+            subfolder: Some("noir".to_string()),
+            revision: Some("v1".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(prepared.commit, Some(v1));
+        let dir = PathBuf::from(prepared.template_path.path.clone().unwrap());
+        assert_eq!(fs::read_to_string(dir.join("README.md")).unwrap(), "v1\n");
+        assert_eq!(dir, prepared.repository_dir.join("noir"));
+
+        // The clone is removed with the prepared template
+        drop(prepared);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_prepare_local_with_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let Some(repo) = template_repository(temp_dir.path()) else {
+            return;
+        };
+        let head = git(&repo, &["rev-parse", "HEAD"]).unwrap();
+        let template_info = TemplateInfo {
+            name: "Noir".to_string(),
+            description: String::new(),
+            repository: format!("{}{}", template::FILE_SCHEME, repo.display()),
+            subfolder: "noir".to_string(),
+            frameworks: vec![],
+            revision: None,
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source: template::TemplateSource::Local,
+        };
+        assert_eq!(
+            prepare(&template_info, None, false).unwrap().commit,
+            Some(head)
+        );
+
+        // HEAD doesn't describe a modified working tree
+        fs::write(repo.join("noir").join("README.md"), "v3\n").unwrap();
+        assert_eq!(prepare(&template_info, None, false).unwrap().commit, None);
+    }
+
+    #[test]
+    fn test_clone_from_mirror() {
+        let temp_dir = TempDir::new().unwrap();
+        let Some(repo) = template_repository(temp_dir.path()) else {
+            return;
+        };
+        let template_info = TemplateInfo {
+            name: "Noir".to_string(),
+            description: String::new(),
+            repository: repo.to_string_lossy().to_string(),
+            subfolder: "noir".to_string(),
+            frameworks: vec![],
+            revision: None,
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source: template::TemplateSource::default(),
+        };
+        let template_path = template_info.template_path(Some("v1".to_string())).unwrap();
+        let cache = TemplateCache::new(temp_dir.path().join("cache"));
+        let err = cache
+            .mirror_template_path(&template_info, Some("v1"), template_path.clone())
+            .unwrap_err();
+        assert_eq!(err.code(), "template_not_cached");
+
+        let (_, v1) = cache.warm(&template_info.repository, Some("v1")).unwrap();
+        let checkouts = cache.list().unwrap()[0].commits.clone();
+        let template_path = cache
+            .mirror_template_path(&template_info, Some("v1"), template_path)
+            .unwrap();
+        let prepared = clone_template(template_path).unwrap();
+        assert_eq!(prepared.commit, Some(v1));
+        let dir = PathBuf::from(prepared.template_path.path.clone().unwrap());
+        assert_eq!(fs::read_to_string(dir.join("README.md")).unwrap(), "v1\n");
+
+        // The cache is only read from
+        assert_eq!(cache.list().unwrap()[0].commits, checkouts);
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let key = cache_key("https://github.com/sripwoud/cza-templates");
+        assert!(key.starts_with("https___github_com_sripwoud_cza_templates-"));
+        assert_eq!(key, cache_key("https://github.com/sripwoud/cza-templates"));
+        assert_ne!(
+            cache_key("https://github.com/acme/a-b"),
+            cache_key("https://github.com/acme/a_b")
+        );
+    }
+
+    #[test]
+    fn test_refuses_mirror_of_another_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        let Some(repo) = template_repository(temp_dir.path()) else {
+            return;
+        };
+        let repository = repo.to_string_lossy().to_string();
+        let cache = TemplateCache::new(temp_dir.path().join("cache"));
+        cache.fetch(&repository).unwrap();
+        assert!(cache.contains(&repository));
+
+        // A directory whose metadata names another repository is never used
+        let metadata = cache.repository_dir(&repository).join(METADATA_FILE_NAME);
+        fs::write(
+            &metadata,
+            "repository = \"https://example.com/other\"\nfetched_at = \"2025-01-01T00:00:00Z\"\n",
+        )
+        .unwrap();
+        assert!(!cache.contains(&repository));
+        assert_eq!(cache.resolve(&repository, None), None);
+        assert!(cache
+            .fetch(&repository)
+            .unwrap_err()
+            .to_string()
+            .contains("holds another repository"));
+        assert!(cache.warm(&repository, None).is_err());
+
+        // Without metadata, the mirror's remote is checked
+        fs::remove_file(&metadata).unwrap();
+        assert!(cache.contains(&repository));
+    }
+}
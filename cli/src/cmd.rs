@@ -4,18 +4,30 @@
 //!
 //! - [`new`] - Create new ZK application projects from templates
 //! - [`list`] - List available templates and frameworks
+//! - [`search`] - Fuzzy search the template registry
+//! - [`info`] - Show a template's README, placeholders and setup steps
 //! - [`config`] - Configure global CLI settings
 //! - [`update`] - Self-update the CLI tool
+//! - [`upgrade`] - Pull template changes into an existing project
+//! - [`diff`] - Compare a project against its originating template
+//! - [`cache`] - Manage the local template cache
+//! - [`doctor`] - Diagnose the environment and toolchain
 //!
 //! All commands implement the [`Execute`] trait for consistent execution and error handling.
 
-use crate::output;
+use crate::{error, output};
 use anyhow::Result;
 
+pub mod cache;
 pub mod config;
+pub mod diff;
+pub mod doctor;
+pub mod info;
 pub mod list;
 pub mod new;
+pub mod search;
 pub mod update;
+pub mod upgrade;
 
 /// Trait for command execution with standardized error handling
 pub trait Execute {
@@ -25,11 +37,21 @@ pub trait Execute {
     /// Execute the command with the given arguments
     fn run(&self, args: &Self::Args) -> Result<()>;
 
-    /// Execute the command and handle errors consistently
+    /// Whether errors should be reported as JSON, for commands with a `--json` flag
+    fn json_output(&self, _args: &Self::Args) -> bool {
+        false
+    }
+
+    /// Execute the command and handle errors consistently, exiting with the error's
+    /// exit code
     fn execute(&self, args: &Self::Args) {
         if let Err(e) = self.run(args) {
-            output::format_error(&e);
-            std::process::exit(1);
+            if self.json_output(args) {
+                output::format_error_json(&e);
+            } else {
+                output::format_error(&e);
+            }
+            std::process::exit(error::exit_code(&e));
         }
     }
 }
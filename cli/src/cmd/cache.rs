@@ -0,0 +1,206 @@
+use super::Execute;
+use crate::{
+    cache::{self, TemplateCache},
+    context, output, render,
+    template::{self, TemplateInfo},
+};
+use anyhow::{anyhow, Context, Result};
+use clap::{Args, Subcommand};
+use log::debug;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+#[derive(Args, Debug)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub command: CacheSubcommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheSubcommand {
+    /// Download templates into the cache so they can be used with --offline
+    Warm {
+        /// Template to cache (all registry templates when omitted)
+        template: Option<String>,
+
+        /// Git revision to cache instead of the template's pinned revision or latest
+        #[arg(long)]
+        revision: Option<String>,
+
+        /// Additional registry to include, as a URL or file path (optionally NAME=LOCATION)
+        #[arg(long = "registry", value_name = "[NAME=]LOCATION")]
+        registries: Vec<template::RegistrySpec>,
+    },
+    /// List cached template repositories
+    List {
+        /// Output cached repositories as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Remove all cached templates and registries
+    Clean,
+}
+
+pub struct CacheCommand;
+
+impl Execute for CacheCommand {
+    type Args = CacheArgs;
+
+    fn json_output(&self, args: &Self::Args) -> bool {
+        matches!(args.command, CacheSubcommand::List { json: true })
+    }
+
+    fn run(&self, args: &Self::Args) -> Result<()> {
+        debug!("Starting cache command: {:#?}", args.command);
+
+        match &args.command {
+            CacheSubcommand::Warm {
+                template,
+                revision,
+                registries,
+            } => self.warm(template.as_deref(), revision.as_deref(), registries),
+            CacheSubcommand::List { json } => {
+                self.list(&TemplateCache::open()?, *json || output::is_json())
+            }
+            CacheSubcommand::Clean => {
+                self.clean(&TemplateCache::open()?, &template::registry_cache_dir()?)
+            }
+        }
+    }
+}
+
+impl CacheCommand {
+    fn warm(
+        &self,
+        template: Option<&str>,
+        revision: Option<&str>,
+        registries: &[template::RegistrySpec],
+    ) -> Result<()> {
+        if context::offline() {
+            return Err(anyhow!("Cannot warm the template cache with --offline"));
+        }
+
+        let config = context::config()?;
+        let registry = template::load_registry(&config, registries)?;
+        let templates: Vec<(&String, &TemplateInfo)> = match template {
+            Some(key) => {
+                let (found, info) = registry.find(key, config.development.autocorrect)?;
+                if found != key {
+                    output::warning(&format!("Template '{}' not found, using '{}'", key, found));
+                }
+                vec![(found, info)]
+            }
+            None => registry.templates.iter().collect(),
+        };
+
+        let cache = TemplateCache::open()?;
+        // Templates sharing a repository and revision only need one checkout
+        let mut warmed = BTreeSet::new();
+        for (key, info) in templates {
+            if info.is_local() {
+                debug!("Skipping local template {}", key);
+                continue;
+            }
+            let revision = revision.or(info.revision.as_deref());
+            if !warmed.insert((info.repository.as_str(), revision)) {
+                continue;
+            }
+
+            output::step(&format!(
+                "Caching {} at {}...",
+                info.repository,
+                revision.unwrap_or("latest")
+            ));
+            let (_, commit) = cache
+                .warm(&info.repository, revision)
+                .with_context(|| format!("Failed to cache template '{}'", key))?;
+            output::success(&format!("Cached {} ({})", key, &commit[..12]));
+        }
+
+        if warmed.is_empty() {
+            output::info("No remote templates to cache");
+        }
+        Ok(())
+    }
+
+    fn list(&self, cache: &TemplateCache, json: bool) -> Result<()> {
+        let repositories = cache.list()?;
+
+        if json {
+            return output::print_json(&repositories)
+                .context("Failed to serialize cached templates");
+        }
+
+        if repositories.is_empty() {
+            output::info("The template cache is empty. Run 'cza cache warm' to fill it");
+            return Ok(());
+        }
+
+        output::header("Cached Templates");
+        for repository in &repositories {
+            output::plain(&format!(
+                "  {} ({}, fetched {})",
+                repository.repository,
+                render::format_size(repository.size),
+                repository.fetched_at
+            ));
+            for commit in &repository.commits {
+                output::plain(&format!("    {}", commit));
+            }
+        }
+        output::info(&format!("Cache directory: {}", cache.root().display()));
+        Ok(())
+    }
+
+    /// Remove the template `cache` and the cached `registries`
+    fn clean(&self, cache: &TemplateCache, registries: &Path) -> Result<()> {
+        let freed = cache.clean()?;
+
+        let freed = if registries.exists() {
+            let size = cache::dir_size(registries);
+            std::fs::remove_dir_all(registries)
+                .with_context(|| format!("Failed to remove {}", registries.display()))?;
+            freed + size
+        } else {
+            freed
+        };
+
+        output::success(&format!(
+            "Removed cached templates and registries ({} freed)",
+            render::format_size(freed)
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cache_list_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = TemplateCache::new(temp_dir.path().join("templates"));
+        assert!(CacheCommand.list(&cache, false).is_ok());
+        assert!(CacheCommand.list(&cache, true).is_ok());
+    }
+
+    #[test]
+    fn test_cache_clean() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = TemplateCache::new(temp_dir.path().join("templates"));
+        fs::create_dir_all(cache.root()).unwrap();
+        let registries = temp_dir.path().join("registries");
+        fs::create_dir_all(&registries).unwrap();
+        fs::write(registries.join("registry.toml"), "").unwrap();
+
+        assert!(CacheCommand.clean(&cache, &registries).is_ok());
+        assert!(!cache.root().exists());
+        assert!(!registries.exists());
+
+        // Cleaning an empty cache is fine
+        assert!(CacheCommand.clean(&cache, &registries).is_ok());
+    }
+}
@@ -1,9 +1,14 @@
 use super::Execute;
-use crate::config::Config;
+use crate::config::{layers, schema, Config, ConfigOrigin, ProfileSet};
+use crate::context;
+use crate::error::CzaError;
 use crate::output;
-use anyhow::{Context, Result};
+use crate::suggest::{NameKind, UnknownName};
+use anyhow::{anyhow, Context, Result};
 use clap::{Args, Subcommand};
 use log::debug;
+use serde::Serialize;
+use std::collections::BTreeMap;
 
 #[derive(Args, Debug)]
 pub struct ConfigArgs {
@@ -26,11 +31,50 @@ pub enum ConfigSubcommand {
         key: String,
     },
     /// List all configuration values
-    List,
-    /// Reset configuration to defaults
-    Reset,
+    List {
+        /// Show where each value comes from (default, file, profile, environment or flag)
+        #[arg(long)]
+        show_origin: bool,
+    },
+    /// Reset configuration to defaults, or a single key when given
+    Reset {
+        /// Configuration key to reset (e.g., user.git_init)
+        key: Option<String>,
+    },
     /// Show the configuration file path
     Path,
+    /// Print the JSON Schema of the configuration file
+    Schema,
+    /// Manage named configuration profiles
+    Profile {
+        #[command(subcommand)]
+        command: ProfileSubcommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProfileSubcommand {
+    /// List the profiles defined in the configuration files
+    List,
+    /// Apply a profile by default, or none when no name is given
+    Use {
+        /// Profile name (a [profiles.<name>] section of the global configuration)
+        name: Option<String>,
+    },
+    /// Show the settings of a profile, the active one by default
+    Show {
+        /// Profile name
+        name: Option<String>,
+    },
+}
+
+/// A configuration value reported by the `result` event of `--output json`
+#[derive(Serialize)]
+struct JsonEntry<'a> {
+    key: &'a str,
+    value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    origin: Option<String>,
 }
 
 pub struct ConfigCommand;
@@ -44,48 +88,236 @@ impl Execute for ConfigCommand {
         match &args.command {
             Some(ConfigSubcommand::Set { key, value }) => {
                 debug!("Setting config key: {} = {}", key, value);
-                let mut config = Config::load()?;
-                config
-                    .set(key, value)
-                    .context(format!("Failed to set {}", key))?;
+                let mut config = Config::load_global()?;
+                let key = &resolve_key(&config, key)?;
+                config.set(key, value)?;
                 config.save().context("Failed to save configuration")?;
                 output::success(&format!("Set {} = {}", key, value));
+                self.warn_if_overridden(key)?;
+                output::result(&JsonEntry {
+                    key,
+                    value: Some(value.clone()),
+                    origin: None,
+                });
             }
             Some(ConfigSubcommand::Get { key }) => {
-                let config = Config::load()?;
-                match config.get(key) {
-                    Some(value) => output::info(&format!("{} = {}", key, value)),
-                    None => output::warning(&format!("Configuration key '{}' not found", key)),
+                let config = context::config()?;
+                let key = &resolve_key(&config, key)?;
+                let value = config.get(key)?;
+                match value {
+                    // Print the bare value for scripts
+                    Some(ref value) if context::quiet() => output::plain(value),
+                    Some(ref value) => output::info(&format!("{} = {}", key, value)),
+                    None => output::warning(&format!("Configuration key '{}' is not set", key)),
                 }
+                output::result(&JsonEntry {
+                    key,
+                    value,
+                    origin: None,
+                });
             }
-            Some(ConfigSubcommand::List) => {
-                let config = Config::load()?;
-                output::header("Configuration Values");
-                for (key, value) in config.list() {
-                    println!("  {} = {}", key, value);
-                }
+            Some(ConfigSubcommand::List { show_origin }) => {
+                self.list(*show_origin)?;
             }
-            Some(ConfigSubcommand::Reset) => {
-                let mut config = Config::load()?;
+            Some(ConfigSubcommand::Reset { key: None }) => {
+                let mut config = Config::load_global()?;
                 config.reset();
                 config.save().context("Failed to save configuration")?;
                 output::success("Configuration reset to defaults");
             }
+            Some(ConfigSubcommand::Reset { key: Some(key) }) => {
+                let mut config = Config::load_global()?;
+                let key = &resolve_key(&config, key)?;
+                config.reset_key(key)?;
+                config.save().context("Failed to save configuration")?;
+                match config.get(key)? {
+                    Some(value) => output::success(&format!("Reset {} = {}", key, value)),
+                    None => output::success(&format!("Reset {} (not set)", key)),
+                }
+            }
             Some(ConfigSubcommand::Path) => {
                 let path = Config::config_path()?;
                 output::info(&format!("Configuration file: {}", path.display()));
+                if let Some(project_path) = Config::project_path() {
+                    output::info(&format!(
+                        "Project configuration file: {}",
+                        project_path.display()
+                    ));
+                }
+            }
+            Some(ConfigSubcommand::Schema) => {
+                output::print_json(&schema::json_schema()).context("Failed to serialize schema")?;
+            }
+            Some(ConfigSubcommand::Profile { command }) => {
+                self.profile(command)?;
             }
             None => {
                 // If no subcommand is provided, show the list
-                let config = Config::load()?;
-                output::header("Configuration Values");
-                for (key, value) in config.list() {
-                    println!("  {} = {}", key, value);
+                self.list(false)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ConfigCommand {
+    /// Warn when a project file, environment variable or flag hides the global value
+    fn warn_if_overridden(&self, key: &str) -> Result<()> {
+        let layered = Config::load_layered()?;
+        if let Some(
+            origin @ (ConfigOrigin::Project(_)
+            | ConfigOrigin::Profile(_)
+            | ConfigOrigin::Env(_)
+            | ConfigOrigin::CommandLine),
+        ) = layered.origins.get(key)
+        {
+            output::warning(&format!("{} is overridden by the {}", key, origin));
+        }
+        Ok(())
+    }
+
+    /// Print the effective configuration, optionally with the origin of each value
+    fn list(&self, show_origin: bool) -> Result<()> {
+        let layered = Config::load_layered()?;
+        if output::is_json() {
+            let values = schema::KEYS
+                .iter()
+                .map(|config_key| {
+                    Ok(JsonEntry {
+                        key: config_key.key,
+                        value: layered.config.get(config_key.key)?,
+                        origin: layered.origins.get(config_key.key).map(ToString::to_string),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            output::result(&serde_json::json!({
+                "profile": layered.profile.as_ref().map(|active| &active.name),
+                "values": values,
+            }));
+            return Ok(());
+        }
+        output::header("Configuration Values");
+        match &layered.profile {
+            Some(active) if show_origin => {
+                output::info(&format!("Profile: {}  ({})", active.name, active.origin))
+            }
+            Some(active) => output::info(&format!("Profile: {}", active.name)),
+            None => output::info("Profile: none"),
+        }
+        for (key, value) in layered.config.list() {
+            match layered.origins.get(key.as_str()) {
+                Some(origin) if show_origin => {
+                    output::plain(&format!("  {} = {}  ({})", key, value, origin))
                 }
+                _ => output::plain(&format!("  {} = {}", key, value)),
             }
         }
         Ok(())
     }
+
+    /// Run a `cza config profile` subcommand
+    fn profile(&self, command: &ProfileSubcommand) -> Result<()> {
+        match command {
+            ProfileSubcommand::List => {
+                let ProfileSet { profiles, active } = Config::load_profiles()?;
+                let active_name = active.as_ref().map(|active| active.name.as_str());
+                output::header("Configuration Profiles");
+                if profiles.is_empty() {
+                    output::info(
+                        "No profiles defined. Add [profiles.<name>] sections to the configuration file.",
+                    );
+                }
+                for name in profiles.keys() {
+                    match &active {
+                        Some(active) if active.name == *name => {
+                            output::plain(&format!("* {}  (active, from {})", name, active.origin))
+                        }
+                        _ => output::plain(&format!("  {}", name)),
+                    }
+                }
+                if let Some(name) = active_name.filter(|name| !profiles.contains_key(*name)) {
+                    output::warning(&format!("Selected profile '{}' is not defined", name));
+                }
+                output::result(&serde_json::json!({
+                    "profiles": profiles.keys().collect::<Vec<_>>(),
+                    "active": active_name,
+                }));
+            }
+            ProfileSubcommand::Use { name: Some(name) } => {
+                let mut config = Config::load_global()?;
+                if !config.profiles.contains_key(name) {
+                    let names = config.profiles.keys().map(String::as_str);
+                    return Err(
+                        CzaError::from(UnknownName::new(NameKind::Profile, name, names)).into(),
+                    );
+                }
+                config.profile = Some(name.clone());
+                config.save().context("Failed to save configuration")?;
+                output::success(&format!("Using profile '{}'", name));
+                if let Some(active) = Config::load_profiles()?.active {
+                    if active.name != *name {
+                        output::warning(&format!(
+                            "Profile '{}' from the {} takes precedence here",
+                            active.name, active.origin
+                        ));
+                    }
+                }
+            }
+            ProfileSubcommand::Use { name: None } => {
+                let mut config = Config::load_global()?;
+                config.profile = None;
+                config.save().context("Failed to save configuration")?;
+                output::success("No profile applied by default");
+            }
+            ProfileSubcommand::Show { name } => {
+                let ProfileSet { profiles, active } = Config::load_profiles()?;
+                let name = match (name, active) {
+                    (Some(name), _) => name.clone(),
+                    (None, Some(active)) => active.name,
+                    (None, None) => {
+                        return Err(anyhow!(
+                            "No profile is active. Pass a profile name or run 'cza config profile use <name>'"
+                        ))
+                    }
+                };
+                let profile = profiles.get(&name).ok_or_else(|| {
+                    CzaError::from(UnknownName::new(
+                        NameKind::Profile,
+                        &name,
+                        profiles.keys().map(String::as_str),
+                    ))
+                })?;
+                output::header(&format!("Profile '{}'", name));
+                let settings = layers::flatten_profile(profile);
+                if settings.is_empty() {
+                    output::info("  (no settings)");
+                }
+                for (key, value) in &settings {
+                    output::plain(&format!("  {} = {}", key, value));
+                }
+                output::result(&serde_json::json!({
+                    "profile": name,
+                    "settings": settings.into_iter().collect::<BTreeMap<_, _>>(),
+                }));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check that `key` exists, autocorrecting it when `development.autocorrect` is enabled
+fn resolve_key(config: &Config, key: &str) -> Result<String> {
+    if schema::find(key).is_some() {
+        return Ok(key.to_string());
+    }
+    let corrected = Config::unknown_key(key)
+        .correct(config.development.autocorrect)
+        .map_err(CzaError::from)?;
+    output::warning(&format!(
+        "Unknown configuration key '{}', using '{}'",
+        key, corrected
+    ));
+    Ok(corrected)
 }
 
 #[cfg(test)]
@@ -152,7 +384,7 @@ mod tests {
 
         let command = ConfigCommand;
         let args = ConfigArgs {
-            command: Some(ConfigSubcommand::List),
+            command: Some(ConfigSubcommand::List { show_origin: true }),
         };
 
         let result = command.run(&args);
@@ -167,13 +399,98 @@ mod tests {
 
         let command = ConfigCommand;
         let args = ConfigArgs {
-            command: Some(ConfigSubcommand::Reset),
+            command: Some(ConfigSubcommand::Reset { key: None }),
         };
 
         let result = command.run(&args);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_config_reset_key() {
+        let _lock = CONFIG_TEST_MUTEX.lock().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        env::set_var("XDG_CONFIG_HOME", temp_dir.path());
+
+        let command = ConfigCommand;
+        command
+            .run(&ConfigArgs {
+                command: Some(ConfigSubcommand::Set {
+                    key: "user.git_init".to_string(),
+                    value: "false".to_string(),
+                }),
+            })
+            .unwrap();
+        command
+            .run(&ConfigArgs {
+                command: Some(ConfigSubcommand::Reset {
+                    key: Some("user.git_init".to_string()),
+                }),
+            })
+            .unwrap();
+        assert!(Config::load().unwrap().user.git_init);
+
+        let result = command.run(&ConfigArgs {
+            command: Some(ConfigSubcommand::Reset {
+                key: Some("user.nothing".to_string()),
+            }),
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_config_schema() {
+        let command = ConfigCommand;
+        let args = ConfigArgs {
+            command: Some(ConfigSubcommand::Schema),
+        };
+        assert!(command.run(&args).is_ok());
+    }
+
+    #[test]
+    fn test_config_profiles() {
+        let _lock = CONFIG_TEST_MUTEX.lock().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        env::set_var("XDG_CONFIG_HOME", temp_dir.path());
+        let config_dir = temp_dir.path().join("cza");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        std::fs::write(
+            config_dir.join("config.toml"),
+            "[profiles.work.user]\nauthor = \"Work Author\"\n",
+        )
+        .unwrap();
+
+        let command = ConfigCommand;
+        let run = |command_args| {
+            command.run(&ConfigArgs {
+                command: Some(ConfigSubcommand::Profile {
+                    command: command_args,
+                }),
+            })
+        };
+        assert!(run(ProfileSubcommand::Show { name: None }).is_err());
+        assert!(run(ProfileSubcommand::Use {
+            name: Some("wrok".to_string())
+        })
+        .is_err());
+
+        run(ProfileSubcommand::Use {
+            name: Some("work".to_string()),
+        })
+        .unwrap();
+        let layered = Config::load_layered().unwrap();
+        assert_eq!(layered.config.user.author.as_deref(), Some("Work Author"));
+        assert_eq!(
+            layered.origins["user.author"],
+            ConfigOrigin::Profile("work".to_string())
+        );
+        assert!(run(ProfileSubcommand::List).is_ok());
+        assert!(run(ProfileSubcommand::Show { name: None }).is_ok());
+
+        run(ProfileSubcommand::Use { name: None }).unwrap();
+        assert_eq!(Config::load().unwrap().user.author, None);
+    }
+
     #[test]
     fn test_config_path() {
         let _lock = CONFIG_TEST_MUTEX.lock().unwrap();
@@ -0,0 +1,253 @@
+use super::Execute;
+use crate::{
+    lock::{ProjectLock, LOCK_FILE_NAME},
+    output,
+    render::{self, FileTree},
+};
+use anyhow::{Context, Result};
+use clap::Args;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use log::debug;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Project directory to compare (defaults to the current directory)
+    project_dir: Option<PathBuf>,
+
+    /// Ignore paths matching a glob (repeatable), e.g. --ignore 'web/**'
+    #[arg(long, value_name = "GLOB")]
+    ignore: Vec<String>,
+
+    /// Only list the files that differ
+    #[arg(long)]
+    name_only: bool,
+
+    /// Output the differences as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+/// How a project file differs from what the template produced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum DiffStatus {
+    /// Contents changed in the project
+    Modified,
+    /// File only exists in the project
+    Added,
+    /// File produced by the template but missing from the project
+    Removed,
+}
+
+impl DiffStatus {
+    fn marker(self) -> char {
+        match self {
+            DiffStatus::Modified => 'M',
+            DiffStatus::Added => 'A',
+            DiffStatus::Removed => 'D',
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DiffEntry {
+    path: PathBuf,
+    status: DiffStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<String>,
+}
+
+pub struct DiffCommand;
+
+impl Execute for DiffCommand {
+    type Args = DiffArgs;
+
+    fn json_output(&self, args: &Self::Args) -> bool {
+        args.json || output::is_json()
+    }
+
+    fn run(&self, args: &Self::Args) -> Result<()> {
+        let project_dir = args
+            .project_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("."));
+        debug!("Starting diff command in {}", project_dir.display());
+
+        let lock = ProjectLock::read(&project_dir)?;
+        let ignores = self.build_ignores(&args.ignore)?;
+        let revision = lock.template.locked_revision();
+        let json = self.json_output(args);
+
+        if !json {
+            output::step(&format!(
+                "Rendering template {} at revision {}...",
+                lock.template.key,
+                revision.unwrap_or("latest")
+            ));
+        }
+
+        // Re-render the template exactly as it was generated
+        let scratch = tempfile::TempDir::new().context("Failed to create temporary directory")?;
+        let rendered = render::render_revision(
+            &lock.template.template_info(),
+            revision,
+            &lock.defines,
+            scratch.path(),
+        )?;
+
+        let template_tree = render::read_worktree(&rendered, &ignores)?;
+        let project_tree = render::read_worktree(&project_dir, &ignores)?;
+        let entries = self.compare(&template_tree, &project_tree, !args.name_only);
+
+        if json {
+            return output::print_json(&entries);
+        }
+
+        if entries.is_empty() {
+            output::success("Project matches the template");
+            return Ok(());
+        }
+
+        for entry in &entries {
+            match &entry.diff {
+                Some(diff) => output::plain(diff),
+                None => output::plain(&format!(
+                    "{} {}",
+                    entry.status.marker(),
+                    entry.path.display()
+                )),
+            }
+        }
+        output::info(&format!(
+            "{} file(s) differ from the template",
+            entries.len()
+        ));
+
+        Ok(())
+    }
+}
+
+impl DiffCommand {
+    /// Glob set of ignored paths: the lock file plus user-provided globs
+    fn build_ignores(&self, globs: &[String]) -> Result<GlobSet> {
+        let mut builder = GlobSetBuilder::new();
+        builder.add(Glob::new(LOCK_FILE_NAME)?);
+        for glob in globs {
+            builder
+                .add(Glob::new(glob).with_context(|| format!("Invalid ignore glob '{}'", glob))?);
+        }
+        builder.build().context("Failed to build ignore globs")
+    }
+
+    /// Compare the rendered template with the project tree
+    fn compare(
+        &self,
+        template_tree: &FileTree,
+        project_tree: &FileTree,
+        with_diff: bool,
+    ) -> Vec<DiffEntry> {
+        render::union_paths(template_tree, project_tree)
+            .into_iter()
+            .filter_map(|path| {
+                let theirs = template_tree.get(path).map(Vec::as_slice);
+                let ours = project_tree.get(path).map(Vec::as_slice);
+                let status = match (theirs, ours) {
+                    (Some(theirs), Some(ours)) if theirs == ours => return None,
+                    (Some(_), Some(_)) => DiffStatus::Modified,
+                    (None, _) => DiffStatus::Added,
+                    (_, None) => DiffStatus::Removed,
+                };
+                Some(DiffEntry {
+                    path: path.clone(),
+                    status,
+                    diff: with_diff.then(|| render::unified_diff(Path::new(path), theirs, ours)),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tree(files: &[(&str, &str)]) -> FileTree {
+        files
+            .iter()
+            .map(|(path, contents)| (PathBuf::from(path), contents.as_bytes().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_compare() {
+        let template = tree(&[
+            ("README.md", "template\n"),
+            ("same.txt", "same\n"),
+            ("removed.txt", "gone\n"),
+        ]);
+        let project = tree(&[
+            ("README.md", "project\n"),
+            ("same.txt", "same\n"),
+            ("added.txt", "new\n"),
+        ]);
+
+        let entries = DiffCommand.compare(&template, &project, true);
+        let statuses: Vec<_> = entries
+            .iter()
+            .map(|e| (e.path.to_string_lossy().to_string(), e.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("README.md".to_string(), DiffStatus::Modified),
+                ("added.txt".to_string(), DiffStatus::Added),
+                ("removed.txt".to_string(), DiffStatus::Removed),
+            ]
+        );
+        let readme_diff = entries[0].diff.as_ref().unwrap();
+        assert!(readme_diff.contains("-template"));
+        assert!(readme_diff.contains("+project"));
+    }
+
+    #[test]
+    fn test_compare_name_only() {
+        let template = tree(&[("README.md", "template\n")]);
+        let project = tree(&[("README.md", "project\n")]);
+
+        let entries = DiffCommand.compare(&template, &project, false);
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].diff.is_none());
+
+        let json = serde_json::to_value(&entries).unwrap();
+        assert_eq!(json[0]["status"], "modified");
+        assert!(json[0].get("diff").is_none());
+    }
+
+    #[test]
+    fn test_build_ignores() {
+        let ignores = DiffCommand.build_ignores(&["web/**".to_string()]).unwrap();
+        assert!(ignores.is_match(LOCK_FILE_NAME));
+        assert!(ignores.is_match("web/src/main.ts"));
+        assert!(!ignores.is_match("README.md"));
+
+        assert!(DiffCommand.build_ignores(&["[".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_diff_requires_lock_file() {
+        let project = TempDir::new().unwrap();
+        let args = DiffArgs {
+            project_dir: Some(project.path().to_path_buf()),
+            ignore: vec![],
+            name_only: false,
+            json: false,
+        };
+
+        let result = DiffCommand.run(&args);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains(LOCK_FILE_NAME));
+    }
+}
@@ -0,0 +1,374 @@
+use super::Execute;
+use crate::{cache::TemplateCache, config::Config, context, output, template, utils};
+use anyhow::{anyhow, Context, Result};
+use clap::Args;
+use console::Color;
+use log::debug;
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct DoctorArgs {
+    /// Output the diagnostics as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            CheckStatus::Pass => Color::Green,
+            CheckStatus::Warn => Color::Yellow,
+            CheckStatus::Fail => Color::Red,
+        }
+    }
+}
+
+/// Result of a single check, with a hint on how to fix anything that isn't passing
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub category: &'static str,
+    pub name: String,
+    pub status: CheckStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// Files and directories used by cza
+#[derive(Debug, Serialize)]
+struct Paths {
+    config: PathBuf,
+    template_cache: PathBuf,
+    registry_cache: PathBuf,
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    paths: &'a Paths,
+    checks: &'a [Check],
+}
+
+/// An external tool used by cza or by generated projects
+struct Tool {
+    command: &'static str,
+    purpose: &'static str,
+    hint: &'static str,
+    /// Config key enabling the post-generation step that runs the tool
+    setting: Option<&'static str>,
+}
+
+/// Tools checked by `cza doctor`, in display order
+const TOOLS: &[Tool] = &[
+    Tool {
+        command: "git",
+        purpose: "template downloads and git init",
+        hint: "Install git from https://git-scm.com/downloads",
+        setting: None,
+    },
+    Tool {
+        command: "mise",
+        purpose: "mise install after generation",
+        hint: "Install mise from https://mise.jdx.dev/getting-started.html",
+        setting: Some("post_generation.auto_install_deps"),
+    },
+    Tool {
+        command: "hk",
+        purpose: "hk install after generation",
+        hint: "Install hk with 'mise use -g hk'",
+        setting: Some("post_generation.auto_setup_hooks"),
+    },
+    Tool {
+        command: "node",
+        purpose: "frontend development servers and builds",
+        hint: "Install Node.js with 'mise use -g node@lts'",
+        setting: None,
+    },
+    Tool {
+        command: "nargo",
+        purpose: "Noir templates",
+        hint: "Install Noir from https://noir-lang.org/docs/getting_started/quick_start",
+        setting: None,
+    },
+    Tool {
+        command: "scarb",
+        purpose: "Cairo templates",
+        hint: "Install Scarb from https://docs.swmansion.com/scarb/download.html",
+        setting: None,
+    },
+];
+
+pub struct DoctorCommand;
+
+impl Execute for DoctorCommand {
+    type Args = DoctorArgs;
+
+    fn json_output(&self, args: &Self::Args) -> bool {
+        args.json || output::is_json()
+    }
+
+    fn run(&self, args: &Self::Args) -> Result<()> {
+        debug!("Starting doctor command");
+
+        let paths = Paths {
+            config: Config::config_path()?,
+            template_cache: TemplateCache::open()?.root().to_path_buf(),
+            registry_cache: template::registry_cache_dir()?,
+        };
+
+        let (config_check, config) = self.check_config(&paths);
+        let mut checks = self.check_tools(&config);
+        checks.push(config_check);
+        checks.push(self.check_cache(&paths));
+
+        if self.json_output(args) {
+            let report = JsonReport {
+                paths: &paths,
+                checks: &checks,
+            };
+            output::print_json(&report).context("Failed to serialize diagnostics")?;
+        } else {
+            self.print_report(&paths, &checks);
+        }
+
+        let failed = checks
+            .iter()
+            .filter(|check| check.status == CheckStatus::Fail)
+            .count();
+        if failed > 0 {
+            return Err(anyhow!(
+                "{} required check{} failed",
+                failed,
+                if failed == 1 { "" } else { "s" }
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl DoctorCommand {
+    /// Detect each tool and its version
+    ///
+    /// git is always required; mise and hk are required when the post-generation steps
+    /// that run them are enabled. Everything else only warns when missing.
+    fn check_tools(&self, config: &Config) -> Vec<Check> {
+        TOOLS
+            .iter()
+            .map(|tool| {
+                let required = match tool.setting {
+                    None => tool.command == "git",
+                    Some(key) => config.get(key).ok().flatten().as_deref() == Some("true"),
+                };
+                let version = utils::detect_version(tool.command);
+                debug!("Detected {}: {:?}", tool.command, version);
+
+                let (status, detail, hint) = match &version {
+                    Some(Some(version)) => (CheckStatus::Pass, version.clone(), None),
+                    Some(None) => (CheckStatus::Pass, "installed".to_string(), None),
+                    None => (
+                        if required {
+                            CheckStatus::Fail
+                        } else {
+                            CheckStatus::Warn
+                        },
+                        format!("not found (needed for {})", tool.purpose),
+                        Some(match tool.setting {
+                            Some(key) if required => {
+                                format!(
+                                    "{}, or disable it with 'cza config set {} false'",
+                                    tool.hint, key
+                                )
+                            }
+                            _ => tool.hint.to_string(),
+                        }),
+                    ),
+                };
+                Check {
+                    category: "tools",
+                    name: tool.command.to_string(),
+                    status,
+                    version: version.flatten(),
+                    detail,
+                    hint,
+                }
+            })
+            .collect()
+    }
+
+    /// Check that the configuration file parses, returning the configuration to use for
+    /// the other checks (defaults when it doesn't)
+    fn check_config(&self, paths: &Paths) -> (Check, Config) {
+        let (status, detail, hint, config) = if !paths.config.exists() {
+            (
+                CheckStatus::Pass,
+                format!(
+                    "{} (not created yet, using defaults)",
+                    paths.config.display()
+                ),
+                None,
+                Config::default(),
+            )
+        } else {
+            match context::config() {
+                Ok(config) => (
+                    CheckStatus::Pass,
+                    paths.config.display().to_string(),
+                    None,
+                    config,
+                ),
+                Err(e) => (
+                    CheckStatus::Fail,
+                    format!("{}: {:#}", paths.config.display(), e),
+                    Some(format!(
+                        "Fix the error above, or move {} aside to start from defaults",
+                        paths.config.display()
+                    )),
+                    Config::default(),
+                ),
+            }
+        };
+
+        let check = Check {
+            category: "config",
+            name: "config file".to_string(),
+            status,
+            version: None,
+            detail,
+            hint,
+        };
+        (check, config)
+    }
+
+    /// Check the template cache for damage
+    fn check_cache(&self, paths: &Paths) -> Check {
+        let cache = TemplateCache::new(paths.template_cache.clone());
+        let (status, detail, hint) = match cache.verify() {
+            Ok(problems) if problems.is_empty() => {
+                let repositories = cache.list().map(|list| list.len()).unwrap_or(0);
+                (
+                    CheckStatus::Pass,
+                    format!(
+                        "{} ({} cached repositor{})",
+                        paths.template_cache.display(),
+                        repositories,
+                        if repositories == 1 { "y" } else { "ies" }
+                    ),
+                    None,
+                )
+            }
+            Ok(problems) => (
+                CheckStatus::Warn,
+                problems.join("; "),
+                Some(
+                    "Run 'cza cache clean' then 'cza cache warm' to rebuild the cache".to_string(),
+                ),
+            ),
+            Err(e) => (
+                CheckStatus::Warn,
+                format!("{:#}", e),
+                Some(
+                    "Run 'cza cache clean' then 'cza cache warm' to rebuild the cache".to_string(),
+                ),
+            ),
+        };
+
+        Check {
+            category: "cache",
+            name: "template cache".to_string(),
+            status,
+            version: None,
+            detail,
+            hint,
+        }
+    }
+
+    fn print_report(&self, paths: &Paths, checks: &[Check]) {
+        for (category, title) in [
+            ("tools", "Tools"),
+            ("config", "Configuration"),
+            ("cache", "Cache"),
+        ] {
+            output::header(title);
+            for check in checks.iter().filter(|check| check.category == category) {
+                output::check(
+                    check.status.label(),
+                    check.status.color(),
+                    &check.name,
+                    &check.detail,
+                    check.hint.as_deref(),
+                );
+            }
+        }
+
+        output::header("Paths");
+        output::plain(&format!("  Config file:     {}", paths.config.display()));
+        output::plain(&format!(
+            "  Template cache:  {}",
+            paths.template_cache.display()
+        ));
+        output::plain(&format!(
+            "  Registry cache:  {}",
+            paths.registry_cache.display()
+        ));
+        output::plain("");
+
+        let count = |status| checks.iter().filter(|c| c.status == status).count();
+        let summary = format!(
+            "{} passed, {} warnings, {} failed",
+            count(CheckStatus::Pass),
+            count(CheckStatus::Warn),
+            count(CheckStatus::Fail)
+        );
+        // Failures are reported by the returned error
+        if count(CheckStatus::Fail) > 0 {
+            output::info(&summary);
+        } else if count(CheckStatus::Warn) > 0 {
+            output::warning(&summary);
+        } else {
+            output::success(&summary);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_tools_required_depends_on_config() {
+        let mut config = Config::default();
+        config.post_generation.auto_install_deps = false;
+        config.post_generation.auto_setup_hooks = false;
+
+        let checks = DoctorCommand.check_tools(&config);
+        assert_eq!(checks.len(), TOOLS.len());
+        // Only git can fail once the post-generation steps are disabled
+        assert!(checks
+            .iter()
+            .filter(|check| check.name != "git")
+            .all(|check| check.status != CheckStatus::Fail));
+        assert!(checks
+            .iter()
+            .filter(|check| check.status != CheckStatus::Pass)
+            .all(|check| check.hint.is_some()));
+    }
+}
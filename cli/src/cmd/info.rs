@@ -0,0 +1,381 @@
+use super::{
+    new::{self, JsonPostGenStep},
+    Execute,
+};
+use crate::{
+    cache::{self, TemplateCache},
+    context, output,
+    template::{
+        self,
+        compat::{self, Incompatibility, TemplateManifest},
+        details::{Hooks, Placeholder, TemplateDetails},
+        prereqs, TemplateInfo, TemplateSource, VersionBounds,
+    },
+};
+use anyhow::Result;
+use clap::Args;
+use log::{debug, warn};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Args, Debug)]
+pub struct InfoArgs {
+    /// The template to describe (e.g., noir-vite, cairo-vite)
+    template: String,
+
+    /// Describe the template at this git revision instead of its pinned revision or latest
+    #[arg(long)]
+    revision: Option<String>,
+
+    /// Output the template details as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Additional registry to include, as a URL or file path (optionally NAME=LOCATION)
+    #[arg(long = "registry", value_name = "[NAME=]LOCATION")]
+    registries: Vec<template::RegistrySpec>,
+}
+
+/// A commit, with its date and subject when the template cache knows them
+#[derive(Debug, Clone, Serialize)]
+struct CommitInfo {
+    commit: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subject: Option<String>,
+}
+
+impl CommitInfo {
+    fn describe(&self) -> String {
+        let short = &self.commit[..12.min(self.commit.len())];
+        match (&self.date, &self.subject) {
+            (Some(date), Some(subject)) => format!("{} ({}) {}", short, date, subject),
+            _ => short.to_string(),
+        }
+    }
+}
+
+/// Template details printed by `cza info --json`
+#[derive(Serialize)]
+struct JsonInfo<'a> {
+    key: &'a str,
+    name: &'a str,
+    description: &'a str,
+    source: TemplateSource,
+    repository: &'a str,
+    subfolder: &'a str,
+    frameworks: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    revision: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latest_commit: Option<&'a CommitInfo>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    requires: &'a BTreeMap<String, String>,
+    #[serde(flatten)]
+    compatibility: &'a VersionBounds,
+    compatible: bool,
+    /// Whether the template files could be read, without them placeholders and hooks
+    /// are unknown
+    fetched: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    readme: Option<&'a str>,
+    placeholders: &'a [Placeholder],
+    hooks: &'a Hooks,
+    post_generation: Vec<JsonPostGenStep>,
+}
+
+pub struct InfoCommand;
+
+impl Execute for InfoCommand {
+    type Args = InfoArgs;
+
+    fn json_output(&self, args: &Self::Args) -> bool {
+        args.json || output::is_json()
+    }
+
+    fn run(&self, args: &Self::Args) -> Result<()> {
+        debug!("Starting info command for template: {}", args.template);
+
+        let json = self.json_output(args);
+        let config = context::config()?;
+        let registry = template::load_registry(&config, &args.registries)?;
+        let (key, template_info) = registry.find(&args.template, config.development.autocorrect)?;
+        if *key != args.template && !json {
+            output::warning(&format!(
+                "Template '{}' not found, using '{}'",
+                args.template, key
+            ));
+        }
+        template::validate_template(template_info)?;
+
+        let revision = args
+            .revision
+            .clone()
+            .or_else(|| template_info.revision.clone());
+
+        // Fetch the template files, through the cache for remote repositories
+        let (details, commit, manifest_bounds) =
+            match cache::prepare(template_info, revision.as_deref(), context::offline()) {
+                Ok(prepared) => {
+                    let details = match prepared.template_path.path {
+                        Some(ref dir) => Some(TemplateDetails::read(Path::new(dir))?),
+                        None => {
+                            debug!("Template cache unavailable, skipping template files");
+                            if !json {
+                                output::warning(
+                                "Template files are unavailable, showing registry information only",
+                            );
+                            }
+                            None
+                        }
+                    };
+                    let manifest_bounds = TemplateManifest::read(&prepared.repository_dir)?
+                        .map(|manifest| manifest.bounds_for(&template_info.subfolder));
+                    (details, prepared.commit, manifest_bounds)
+                }
+                Err(e) => {
+                    warn!("Could not fetch template {}: {:#}", key, e);
+                    if !json {
+                        output::warning(&format!("Could not fetch template files: {:#}", e));
+                    }
+                    (None, None, None)
+                }
+            };
+
+        let latest_commit = self.latest_commit(template_info, revision.as_deref(), &commit);
+        let incompatibility = [Some(&template_info.compatibility), manifest_bounds.as_ref()]
+            .into_iter()
+            .flatten()
+            .find_map(|bounds| bounds.check().ok().flatten());
+        let fetched = details.is_some();
+        let details = details.unwrap_or_default();
+        let plan = new::post_generation_plan(&config, false);
+
+        if json {
+            let json_info = JsonInfo {
+                key,
+                name: &template_info.name,
+                description: &template_info.description,
+                source: template_info.source,
+                repository: &template_info.repository,
+                subfolder: &template_info.subfolder,
+                frameworks: &template_info.frameworks,
+                revision: revision.as_deref(),
+                commit: revision.as_ref().and(commit.as_deref()),
+                latest_commit: latest_commit.as_ref(),
+                requires: &template_info.requires,
+                compatibility: &template_info.compatibility,
+                compatible: incompatibility.is_none(),
+                fetched,
+                readme: details.readme.as_deref(),
+                placeholders: &details.placeholders,
+                hooks: &details.hooks,
+                // There's no project yet, the editor step shows a placeholder for it
+                post_generation: plan
+                    .iter()
+                    .map(|step| JsonPostGenStep::new(step, Path::new("<project>")))
+                    .collect(),
+            };
+            return output::print_json(&json_info);
+        }
+
+        output::header(&format!("{} ({})", template_info.name, key));
+        output::info(&format!("Description: {}", template_info.description));
+        output::info(&format!("Repository: {}", template_info.repository));
+        if !template_info.subfolder.is_empty() {
+            output::info(&format!("Subfolder: {}", template_info.subfolder));
+        }
+        if !template_info.frameworks.is_empty() {
+            output::info(&format!(
+                "Frameworks: {}",
+                template_info.frameworks.join(", ")
+            ));
+        }
+        output::info(&format!("Source: {}", template_info.source));
+        match (&revision, &commit) {
+            (Some(rev), Some(commit)) => output::info(&format!(
+                "📌 Pinned to: {} ({})",
+                rev,
+                &commit[..12.min(commit.len())]
+            )),
+            (Some(rev), None) => output::info(&format!("📌 Pinned to: {}", rev)),
+            (None, _) => output::info("📌 Pinned to: latest"),
+        }
+        match &latest_commit {
+            Some(latest) => output::info(&format!("Latest commit: {}", latest.describe())),
+            None => output::info("Latest commit: unknown"),
+        }
+        if !template_info.requires.is_empty() {
+            output::info(&format!(
+                "🧰 Requires: {}",
+                prereqs::format_requirements(&template_info.requires)
+            ));
+        }
+        if let Some(incompatibility) = incompatibility {
+            self.print_incompatibility(&incompatibility);
+        }
+
+        output::step("Placeholders:");
+        if !fetched {
+            output::info("  (unknown, template files unavailable)");
+        } else if details.placeholders.is_empty() {
+            output::info("  (none)");
+        }
+        for placeholder in &details.placeholders {
+            output::info(&format!("  {}", describe_placeholder(placeholder)));
+        }
+        output::info("  Set values with --define NAME=VALUE or --values-file");
+
+        output::step("Template hooks:");
+        if !fetched {
+            output::info("  (unknown, template files unavailable)");
+        } else if details.hooks.is_empty() {
+            output::info("  (none)");
+        }
+        for (stage, scripts) in [
+            ("init", &details.hooks.init),
+            ("pre", &details.hooks.pre),
+            ("post", &details.hooks.post),
+        ] {
+            if !scripts.is_empty() {
+                output::info(&format!("  {}: {}", stage, scripts.join(", ")));
+            }
+        }
+
+        output::step("Post-generation steps:");
+        for (index, step) in plan.iter().enumerate() {
+            let status = if step.enabled { "run" } else { "skip" };
+            output::info(&format!(
+                "  {}. [{}] {} ({})",
+                index + 1,
+                status,
+                step.action,
+                step.reason
+            ));
+        }
+
+        if let Some(ref readme) = details.readme {
+            output::header("README");
+            output::plain(readme.trim_end());
+        }
+
+        output::plain("");
+
+        output::command_example(
+            "Create a project from this template",
+            &format!("cza new my-app --template {}", key),
+        );
+        Ok(())
+    }
+}
+
+impl InfoCommand {
+    /// Latest commit of the template repository, described from the cache when possible
+    fn latest_commit(
+        &self,
+        template_info: &TemplateInfo,
+        revision: Option<&str>,
+        prepared_commit: &Option<String>,
+    ) -> Option<CommitInfo> {
+        let cache = TemplateCache::open().ok();
+        // Without a revision the prepared commit already is the latest one
+        let commit = match revision {
+            None => prepared_commit.clone(),
+            Some(_) => None,
+        }
+        .or_else(|| {
+            cache
+                .as_ref()
+                .and_then(|cache| cache.resolve(&template_info.repository, None))
+        })
+        .or_else(|| template::resolve_commit(template_info, None))?;
+
+        let summary = cache
+            .as_ref()
+            .and_then(|cache| cache.describe(&template_info.repository, &commit));
+        Some(CommitInfo {
+            commit,
+            date: summary.as_ref().map(|s| s.date.clone()),
+            subject: summary.map(|s| s.subject),
+        })
+    }
+
+    fn print_incompatibility(&self, incompatibility: &Incompatibility) {
+        output::warning(&format!(
+            "⛔ Incompatible: {} (this is cza {}). {}",
+            incompatibility,
+            compat::current_version(),
+            incompatibility.hint()
+        ));
+    }
+}
+
+/// One-line description of a placeholder, e.g. `backend (string): Which backend? [a, b] default: a`
+fn describe_placeholder(placeholder: &Placeholder) -> String {
+    let mut line = format!("{} ({})", placeholder.name, placeholder.kind);
+    if let Some(ref prompt) = placeholder.prompt {
+        line.push_str(&format!(": {}", prompt));
+    }
+    if !placeholder.choices.is_empty() {
+        line.push_str(&format!(" [{}]", placeholder.choices.join(", ")));
+    }
+    if let Some(ref default) = placeholder.default {
+        line.push_str(&format!(" default: {}", default));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_placeholder() {
+        let mut placeholder = Placeholder {
+            name: "backend".to_string(),
+            kind: "string".to_string(),
+            prompt: Some("Which proving backend?".to_string()),
+            choices: vec!["barretenberg".to_string(), "plonky2".to_string()],
+            default: Some("barretenberg".to_string()),
+            regex: None,
+        };
+        assert_eq!(
+            describe_placeholder(&placeholder),
+            "backend (string): Which proving backend? [barretenberg, plonky2] default: barretenberg"
+        );
+
+        placeholder.prompt = None;
+        placeholder.choices.clear();
+        placeholder.default = None;
+        assert_eq!(describe_placeholder(&placeholder), "backend (string)");
+    }
+
+    #[test]
+    fn test_commit_info_describe() {
+        let mut commit = CommitInfo {
+            commit: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            date: None,
+            subject: None,
+        };
+        assert_eq!(commit.describe(), "0123456789ab");
+        commit.date = Some("2025-01-31".to_string());
+        commit.subject = Some("Bump deps".to_string());
+        assert_eq!(commit.describe(), "0123456789ab (2025-01-31) Bump deps");
+    }
+
+    #[test]
+    fn test_info_command_unknown_template() {
+        let args = InfoArgs {
+            template: "nonexistent".to_string(),
+            revision: None,
+            json: false,
+            registries: vec![],
+        };
+        let err = InfoCommand.run(&args).unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+}
@@ -1,9 +1,17 @@
 use super::Execute;
-use crate::{output, template};
+use crate::{
+    context, output,
+    template::{
+        self,
+        compat::{self, Incompatibility},
+        prereqs, TemplateInfo, TemplateSource, VersionBounds,
+    },
+};
 use anyhow::Result;
-use clap::Args;
+use clap::{Args, ValueEnum};
 use log::{debug, warn};
 use serde::Serialize;
+use std::collections::BTreeMap;
 
 #[derive(Args, Debug)]
 pub struct ListArgs {
@@ -14,6 +22,31 @@ pub struct ListArgs {
     /// Output templates as JSON
     #[arg(long)]
     json: bool,
+
+    /// Only show templates including this framework (repeatable, all must match)
+    #[arg(long = "framework", value_name = "FRAMEWORK")]
+    frameworks: Vec<String>,
+
+    /// Order in which templates are shown
+    #[arg(long, value_enum, default_value_t = SortOrder::Key)]
+    sort: SortOrder,
+
+    /// Additional registry to include, as a URL or file path (optionally NAME=LOCATION)
+    #[arg(long = "registry", value_name = "[NAME=]LOCATION")]
+    registries: Vec<template::RegistrySpec>,
+}
+
+/// Order of listed templates
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Template key, alphabetically
+    Key,
+    /// Display name, alphabetically
+    Name,
+    /// Where the template is defined (built-in, registries, config, local)
+    Source,
+    /// Best search matches first (same as key for `cza list`)
+    Relevance,
 }
 
 #[derive(Serialize)]
@@ -26,6 +59,13 @@ struct JsonTemplate {
     frameworks: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     revision: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    requires: BTreeMap<String, String>,
+    #[serde(flatten)]
+    compatibility: VersionBounds,
+    /// Whether the running cza version is within the template's supported range
+    compatible: bool,
+    source: TemplateSource,
 }
 
 pub struct ListCommand;
@@ -33,84 +73,56 @@ pub struct ListCommand;
 impl Execute for ListCommand {
     type Args = ListArgs;
 
+    fn json_output(&self, args: &Self::Args) -> bool {
+        args.json || output::is_json()
+    }
+
     fn run(&self, args: &Self::Args) -> Result<()> {
         debug!(
-            "Starting list command with detailed: {}, json: {}",
-            args.detailed, args.json
+            "Starting list command with detailed: {}, json: {}, frameworks: {:?}, sort: {:?}",
+            args.detailed, args.json, args.frameworks, args.sort
         );
 
-        // Load embedded template registry
-        debug!("Loading embedded template registry");
-        let registry = template::load_template_registry()?;
+        // Load embedded template registry merged with user templates
+        debug!("Loading template registry");
+        let config = context::config()?;
+        let registry = template::load_registry(&config, &args.registries)?;
         debug!("Found {} templates", registry.templates.len());
 
+        let json = self.json_output(args);
         if registry.templates.is_empty() {
             warn!("No templates available in registry");
-            if args.json {
-                println!("[]");
+            if json {
+                print_json(&[])?;
             } else {
                 output::warning("No templates available.");
             }
             return Ok(());
         }
 
-        // Sort templates by name for consistent output
-        let mut templates: Vec<_> = registry.templates.iter().collect();
-        templates.sort_by_key(|(key, _)| *key);
+        let mut templates: Vec<_> = registry
+            .templates
+            .iter()
+            .filter(|(_, info)| has_frameworks(info, &args.frameworks))
+            .collect();
+        sort_templates(&mut templates, args.sort);
 
         // Handle JSON output
-        if args.json {
-            let json_templates: Vec<JsonTemplate> = templates
-                .iter()
-                .map(|(key, info)| JsonTemplate {
-                    key: (*key).clone(),
-                    name: info.name.clone(),
-                    description: info.description.clone(),
-                    repository: info.repository.clone(),
-                    subfolder: info.subfolder.clone(),
-                    frameworks: info.frameworks.clone(),
-                    revision: info.revision.clone(),
-                })
-                .collect();
-
-            let json_output = serde_json::to_string_pretty(&json_templates)?;
-            println!("{}", json_output);
+        if json {
+            return print_json(&templates);
+        }
+
+        if templates.is_empty() {
+            output::warning(&format!(
+                "No templates include all of: {}",
+                args.frameworks.join(", ")
+            ));
             return Ok(());
         }
 
         // Regular formatted output
         output::header("Available templates");
-
-        for (template_key, template_info) in templates {
-            if args.detailed {
-                // Build full URL to template subfolder
-                let template_url = if template_info.repository.contains("github.com") {
-                    format!(
-                        "{}/tree/main/{}",
-                        template_info.repository, template_info.subfolder
-                    )
-                } else {
-                    template_info.repository.clone()
-                };
-                output::template_detailed(
-                    template_key,
-                    &template_info.name,
-                    &template_info.description,
-                    &template_info.frameworks,
-                    &template_url,
-                );
-                // Show pinned revision if present
-                if let Some(ref revision) = template_info.revision {
-                    output::info(&format!("    📌 Pinned to: {}", revision));
-                }
-            } else {
-                output::template_item(template_key, &template_info.description);
-                // Show pinned indicator in summary view
-                if template_info.revision.is_some() {
-                    output::info("      📌 (pinned)");
-                }
-            }
-        }
+        print_templates(&templates, args.detailed);
 
         if !args.detailed {
             output::info("Use 'cza list --detailed' for more information about templates.");
@@ -126,6 +138,115 @@ impl Execute for ListCommand {
 
 impl ListCommand {}
 
+/// Whether a template includes every framework in `frameworks` (case-insensitive)
+pub(crate) fn has_frameworks(info: &TemplateInfo, frameworks: &[String]) -> bool {
+    frameworks.iter().all(|wanted| {
+        info.frameworks
+            .iter()
+            .any(|framework| framework.eq_ignore_ascii_case(wanted))
+    })
+}
+
+/// Sort templates in place; `Relevance` keeps the current order
+pub(crate) fn sort_templates(templates: &mut [(&String, &TemplateInfo)], order: SortOrder) {
+    match order {
+        SortOrder::Key => templates.sort_by_key(|(key, _)| *key),
+        SortOrder::Name => templates.sort_by(|(a_key, a), (b_key, b)| {
+            a.name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then_with(|| a_key.cmp(b_key))
+        }),
+        SortOrder::Source => templates.sort_by(|(a_key, a), (b_key, b)| {
+            a.source.cmp(&b.source).then_with(|| a_key.cmp(b_key))
+        }),
+        SortOrder::Relevance => {}
+    }
+}
+
+/// Print templates as a JSON array
+pub(crate) fn print_json(templates: &[(&String, &TemplateInfo)]) -> Result<()> {
+    let json_templates: Vec<JsonTemplate> = templates
+        .iter()
+        .map(|(key, info)| JsonTemplate {
+            key: (*key).clone(),
+            name: info.name.clone(),
+            description: info.description.clone(),
+            repository: info.repository.clone(),
+            subfolder: info.subfolder.clone(),
+            frameworks: info.frameworks.clone(),
+            revision: info.revision.clone(),
+            requires: info.requires.clone(),
+            compatibility: info.compatibility.clone(),
+            compatible: incompatibility(info).is_none(),
+            source: info.source,
+        })
+        .collect();
+
+    output::print_json(&json_templates)
+}
+
+/// Print templates in the summary or detailed format
+pub(crate) fn print_templates(templates: &[(&String, &TemplateInfo)], detailed: bool) {
+    for (template_key, template_info) in templates {
+        if detailed {
+            // Build full URL to template subfolder
+            let template_url = if template_info.repository.contains("github.com") {
+                format!(
+                    "{}/tree/main/{}",
+                    template_info.repository, template_info.subfolder
+                )
+            } else {
+                template_info.repository.clone()
+            };
+            output::template_detailed(
+                template_key,
+                &template_info.name,
+                &template_info.description,
+                &template_info.frameworks,
+                &template_url,
+            );
+            // Show pinned revision if present
+            if let Some(ref revision) = template_info.revision {
+                output::info(&format!("    📌 Pinned to: {}", revision));
+            }
+            if let Some(incompatibility) = incompatibility(template_info) {
+                output::warning(&format!(
+                    "    ⛔ Incompatible: {} (this is cza {})",
+                    incompatibility,
+                    compat::current_version()
+                ));
+            }
+            if !template_info.requires.is_empty() {
+                output::info(&format!(
+                    "    🧰 Requires: {}",
+                    prereqs::format_requirements(&template_info.requires)
+                ));
+            }
+            output::info(&format!("    🗂️ Source: {}", template_info.source));
+        } else {
+            output::template_item(template_key, &template_info.description);
+            // Show pinned indicator in summary view
+            if template_info.revision.is_some() {
+                output::info("      📌 (pinned)");
+            }
+            // Flag templates this cza version can't generate
+            if let Some(incompatibility) = incompatibility(template_info) {
+                output::warning(&format!("      ⛔ ({})", incompatibility));
+            }
+            // Mark templates coming from the user's config
+            if template_info.source == TemplateSource::User {
+                output::info("      👤 (user config)");
+            }
+        }
+    }
+}
+
+/// Why the running cza version can't use a template, treating invalid bounds as compatible
+fn incompatibility(info: &TemplateInfo) -> Option<Incompatibility> {
+    info.compatibility.check().ok().flatten()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,6 +257,9 @@ mod tests {
         let args = ListArgs {
             detailed: false,
             json: false,
+            frameworks: vec![],
+            sort: SortOrder::Key,
+            registries: vec![],
         };
         // Should not panic and should return Ok
         assert!(cmd.run(&args).is_ok());
@@ -147,6 +271,9 @@ mod tests {
         let args = ListArgs {
             detailed: true,
             json: false,
+            frameworks: vec![],
+            sort: SortOrder::Key,
+            registries: vec![],
         };
         // Should not panic and should return Ok
         assert!(cmd.run(&args).is_ok());
@@ -158,6 +285,9 @@ mod tests {
         let args = ListArgs {
             detailed: false,
             json: true,
+            frameworks: vec![],
+            sort: SortOrder::Key,
+            registries: vec![],
         };
         // Should not panic and should return Ok
         assert!(cmd.run(&args).is_ok());
@@ -170,6 +300,9 @@ mod tests {
         let args = ListArgs {
             detailed: false,
             json: true,
+            frameworks: vec![],
+            sort: SortOrder::Key,
+            registries: vec![],
         };
 
         // Just verify command executes successfully
@@ -185,6 +318,10 @@ mod tests {
             subfolder: "test".to_string(),
             frameworks: vec!["noir".to_string(), "vite".to_string()],
             revision: None,
+            requires: Default::default(),
+            compatibility: Default::default(),
+            compatible: true,
+            source: TemplateSource::User,
         };
 
         // Should serialize without error
@@ -196,6 +333,7 @@ mod tests {
         let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
         assert_eq!(parsed["key"], "test-template");
         assert_eq!(parsed["frameworks"][0], "noir");
+        assert_eq!(parsed["source"], "user");
     }
 
     #[test]
@@ -221,6 +359,9 @@ mod tests {
             subfolder: "test-template".to_string(),
             frameworks: vec!["test".to_string(), "framework".to_string()],
             revision: None,
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source: TemplateSource::Builtin,
         };
 
         // Test that template has expected properties
@@ -229,6 +370,60 @@ mod tests {
         assert!(template.frameworks.contains(&"test".to_string()));
     }
 
+    fn info(name: &str, frameworks: &[&str], source: TemplateSource) -> TemplateInfo {
+        TemplateInfo {
+            name: name.to_string(),
+            description: String::new(),
+            repository: "https://github.com/test/test".to_string(),
+            subfolder: String::new(),
+            frameworks: frameworks.iter().map(|f| f.to_string()).collect(),
+            revision: None,
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source,
+        }
+    }
+
+    #[test]
+    fn test_has_frameworks() {
+        let template = info("Noir", &["noir", "React"], TemplateSource::Builtin);
+        assert!(has_frameworks(&template, &[]));
+        assert!(has_frameworks(&template, &["noir".to_string()]));
+        assert!(has_frameworks(
+            &template,
+            &["NOIR".to_string(), "react".to_string()]
+        ));
+        assert!(!has_frameworks(
+            &template,
+            &["noir".to_string(), "cairo".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_sort_templates() {
+        let (a, b, c) = ("a".to_string(), "b".to_string(), "c".to_string());
+        let zeta = info("zeta", &[], TemplateSource::Builtin);
+        let alpha = info("Alpha", &[], TemplateSource::User);
+        let beta = info("beta", &[], TemplateSource::Registry);
+        let keys = |templates: &[(&String, &TemplateInfo)]| {
+            templates
+                .iter()
+                .map(|(key, _)| key.as_str())
+                .collect::<Vec<_>>()
+                .join("")
+        };
+
+        let mut templates = vec![(&b, &alpha), (&c, &beta), (&a, &zeta)];
+        sort_templates(&mut templates, SortOrder::Relevance);
+        assert_eq!(keys(&templates), "bca");
+        sort_templates(&mut templates, SortOrder::Key);
+        assert_eq!(keys(&templates), "abc");
+        sort_templates(&mut templates, SortOrder::Name);
+        assert_eq!(keys(&templates), "bca");
+        sort_templates(&mut templates, SortOrder::Source);
+        assert_eq!(keys(&templates), "acb");
+    }
+
     #[test]
     fn test_templates_sorting() {
         let registry = template::load_template_registry().unwrap();
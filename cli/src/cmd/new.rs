@@ -1,14 +1,34 @@
 use super::Execute;
-use crate::{config::Config, output, template, utils};
-use anyhow::{anyhow, Result};
-use cargo_generate::{generate, GenerateArgs, TemplatePath};
-use clap::Args;
+use crate::{
+    cache,
+    config::Config,
+    context,
+    error::CzaError,
+    lock::{LockedTemplate, ProjectLock},
+    output, render,
+    run_log::RunLog,
+    template::{
+        self,
+        compat::{self, TemplateManifest},
+        VersionBounds,
+    },
+    utils, Cli,
+};
+use anyhow::{anyhow, Context, Result};
+use cargo_generate::{generate, GenerateArgs};
+use clap::{Args, CommandFactory};
 use log::{debug, info, warn};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
-#[derive(Args, Debug)]
+mod wizard;
+
+#[derive(Args, Debug, Clone)]
 pub struct NewArgs {
-    /// The name of the new project
-    project_name: String,
+    /// The name of the new project (prompted for in a terminal when omitted)
+    project_name: Option<String>,
 
     /// The template to use (e.g., noir-vite, cairo-vite). If not provided, uses default_template from config
     #[arg(short, long)]
@@ -18,17 +38,156 @@ pub struct NewArgs {
     #[arg(long)]
     author: Option<String>,
 
+    /// Set a template placeholder value (repeatable), e.g. --define backend=barretenberg
+    #[arg(short, long = "define", value_name = "KEY=VALUE", value_parser = template::parse_define)]
+    defines: Vec<(String, String)>,
+
+    /// Read template placeholder values from a TOML file
+    #[arg(long, value_name = "FILE")]
+    values_file: Option<PathBuf>,
+
     /// Skip git initialization (overrides config setting)
     #[arg(long)]
     no_git: bool,
 
-    /// Preview template structure without creating files
+    /// Generate even if tools required by the template are missing or outdated
+    #[arg(long)]
+    skip_prereq_check: bool,
+
+    /// Render the template into a staging directory and preview the result without creating files
     #[arg(long)]
     dry_run: bool,
 
+    /// Output the dry-run generation plan as JSON
+    #[arg(long, requires = "dry_run")]
+    json: bool,
+
     /// Pin template to specific git revision (commit SHA, tag, or branch)
     #[arg(long)]
     revision: Option<String>,
+
+    /// Additional registry to include, as a URL or file path (optionally NAME=LOCATION)
+    #[arg(long = "registry", value_name = "[NAME=]LOCATION")]
+    registries: Vec<template::RegistrySpec>,
+
+    /// Generate from a local template directory or git checkout instead of the registry
+    #[arg(long, value_name = "DIR", conflicts_with = "template")]
+    path: Option<PathBuf>,
+}
+
+/// Action run on a freshly generated project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PostGenAction {
+    GitInit,
+    InstallDeps,
+    SetupHooks,
+    OpenEditor(String),
+}
+
+impl PostGenAction {
+    /// Stable identifier used in JSON output
+    pub(crate) fn id(&self) -> &'static str {
+        match self {
+            PostGenAction::GitInit => "git_init",
+            PostGenAction::InstallDeps => "install_deps",
+            PostGenAction::SetupHooks => "setup_hooks",
+            PostGenAction::OpenEditor(_) => "open_editor",
+        }
+    }
+
+    /// Program and arguments run in `project_dir`
+    fn command(&self, project_dir: &Path) -> Vec<String> {
+        match self {
+            PostGenAction::GitInit => vec!["git".to_string(), "init".to_string()],
+            PostGenAction::InstallDeps => vec!["mise".to_string(), "install".to_string()],
+            PostGenAction::SetupHooks => vec!["hk".to_string(), "install".to_string()],
+            PostGenAction::OpenEditor(editor) => {
+                vec![editor.clone(), project_dir.display().to_string()]
+            }
+        }
+    }
+}
+
+impl fmt::Display for PostGenAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PostGenAction::GitInit => write!(f, "git init"),
+            PostGenAction::InstallDeps => write!(f, "mise install"),
+            PostGenAction::SetupHooks => write!(f, "hk install"),
+            PostGenAction::OpenEditor(editor) => write!(f, "{} <project>", editor),
+        }
+    }
+}
+
+/// A post-generation step, with whether it runs for the current config and flags
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PostGenStep {
+    pub(crate) action: PostGenAction,
+    pub(crate) enabled: bool,
+    /// Why the step runs or is skipped
+    pub(crate) reason: String,
+}
+
+impl PostGenStep {
+    fn new(action: PostGenAction, enabled: bool, reason: &str) -> Self {
+        Self {
+            action,
+            enabled,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Generation plan printed by `--dry-run --json`
+#[derive(Serialize)]
+struct JsonPlan<'a> {
+    template: &'a str,
+    name: &'a str,
+    repository: &'a str,
+    subfolder: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    revision: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit: Option<String>,
+    destination: PathBuf,
+    defines: &'a BTreeMap<String, String>,
+    files: Vec<JsonFile>,
+    post_generation: Vec<JsonPostGenStep>,
+    /// Checks that would stop the generation
+    failed_checks: &'a [String],
+}
+
+/// Generated project reported by the `result` event of `--output json`
+#[derive(Serialize)]
+struct JsonProject<'a> {
+    path: &'a Path,
+    lock: &'a ProjectLock,
+}
+
+#[derive(Serialize)]
+struct JsonFile {
+    path: PathBuf,
+    size: u64,
+}
+
+/// A post-generation step in the JSON output of `cza new --dry-run` and `cza info`
+#[derive(Serialize)]
+pub(crate) struct JsonPostGenStep {
+    action: &'static str,
+    command: Vec<String>,
+    enabled: bool,
+    reason: String,
+}
+
+impl JsonPostGenStep {
+    pub(crate) fn new(step: &PostGenStep, project_dir: &Path) -> Self {
+        Self {
+            action: step.action.id(),
+            command: step.action.command(project_dir),
+            enabled: step.enabled,
+            reason: step.reason.clone(),
+        }
+    }
 }
 
 pub struct NewCommand;
@@ -36,84 +195,126 @@ pub struct NewCommand;
 impl Execute for NewCommand {
     type Args = NewArgs;
 
+    fn json_output(&self, args: &Self::Args) -> bool {
+        args.json
+    }
+
     fn run(&self, args: &Self::Args) -> Result<()> {
+        // Without a terminal to prompt in, a missing name stays a usage error
+        if args.project_name.is_none() && !self.can_prompt(args) {
+            return Err(missing_project_name().into());
+        }
+
+        // A dry run doesn't create anything, not even a log
+        let log = if args.dry_run {
+            RunLog::disabled()
+        } else {
+            RunLog::create("new")
+        };
+        let result = self.create(args, &log);
+        if let Err(ref e) = result {
+            log.line(&format!("Error: {:#}", e));
+            if let Some(path) = log.path() {
+                output::info(&format!("Details of this run are in {}", path.display()));
+            }
+        }
+        result
+    }
+}
+
+impl NewCommand {
+    /// Create the project, recording the run in `log`
+    fn create(&self, args: &NewArgs, log: &RunLog) -> Result<()> {
         // Load configuration
         debug!("Loading configuration");
-        let config = Config::load()?;
+        let mut config = context::config()?;
+
+        // Prompt for missing inputs when running in a terminal
+        let prompted_args;
+        let args = if self.needs_wizard(args, &config) && self.can_prompt(args) {
+            debug!("Missing inputs, starting interactive wizard");
+            let answers = wizard::prompt(
+                args,
+                &config,
+                |name| self.validate_project_name(name, &config),
+                self.resolve_author(args, &config),
+            )?;
+            let mut answered = args.clone();
+            answers.apply(&mut answered, &mut config);
+            prompted_args = answered;
+            &prompted_args
+        } else {
+            args
+        };
+        log.config(&config);
 
-        // Resolve template name from args or config
-        let template_name = match &args.template {
-            Some(template) => template.clone(),
-            None => config
-                .user
-                .default_template
-                .clone()
-                .ok_or_else(|| anyhow!("No template specified and no default_template configured. Use 'cza config set user.default_template <template>' to set a default, or specify a template: 'cza new <template> <project_name>'"))?,
+        let project_name = args
+            .project_name
+            .clone()
+            .context("The wizard didn't set a project name")?;
+
+        // Resolve template from --path, args or config
+        let (template_name, template_info) = match &args.path {
+            Some(path) => {
+                debug!("Using local template directory: {}", path.display());
+                template::local_template(path)?
+            }
+            None => self.resolve_registry_template(args, &config)?,
         };
 
         debug!(
             "Starting new command with template: {}, project: {}",
-            template_name, args.project_name
+            template_name, project_name
         );
 
-        if args.dry_run {
+        if args.json {
+            debug!("JSON output requested, skipping progress messages");
+        } else if args.dry_run {
             output::step(&format!(
                 "Previewing {} template structure for project: {}",
-                template_name, args.project_name
+                template_name, project_name
             ));
         } else {
             output::step(&format!(
                 "Creating new {} project: {}",
-                template_name, args.project_name
+                template_name, project_name
             ));
         }
 
-        // Load embedded template registry
-        debug!("Loading embedded template registry");
-        let registry = template::load_template_registry()?;
-
-        // Look up template
-        debug!("Looking up template: {}", template_name);
-        let template_info = registry.templates.get(&template_name).ok_or_else(|| {
-            anyhow!(
-                "Template '{}' not found. Use 'cza list' to see available templates.",
-                template_name
-            )
-        })?;
-
         debug!(
-            "Found template: {} - {}",
-            template_info.name, template_info.description
+            "Found {} template: {} - {}",
+            template_info.source, template_info.name, template_info.description
         );
-        output::info(&format!("Using template: {}", template_info.name));
-        output::info(&format!("Description: {}", template_info.description));
+        if !args.json {
+            output::info(&format!("Using template: {}", template_info.name));
+            output::info(&format!("Description: {}", template_info.description));
+        }
 
-        // If dry-run, show preview and exit
-        if args.dry_run {
-            return self.preview_template(args, &template_name, template_info);
+        template::validate_template(&template_info)?;
+
+        // A dry run reports failed checks in the preview instead of stopping at them
+        let mut failed_checks = Vec::new();
+        let mut check = |result: Result<()>| match result {
+            Err(e) if args.dry_run => {
+                debug!("Check failed: {:#}", e);
+                failed_checks.push(format!("{:#}", e));
+                Ok(())
+            }
+            result => result,
+        };
+        check(self.check_compatibility(&template_name, &template_info.compatibility))?;
+        if args.skip_prereq_check {
+            debug!("Skipping prerequisite check");
+        } else {
+            check(self.check_prerequisites(&template_name, &template_info))?;
         }
 
         // Validate project name
-        debug!("Validating project name: {}", args.project_name);
-        self.validate_project_name(&args.project_name, &config)?;
+        debug!("Validating project name: {}", project_name);
+        self.validate_project_name(&project_name, &config)?;
 
         // Set author from arg, config, or git config
-        debug!("Resolving author information");
-        let author = args
-            .author
-            .clone()
-            .or_else(|| {
-                debug!("No author arg provided, checking config");
-                config.user.author.clone()
-            })
-            .or_else(|| {
-                debug!("No author in config, trying git config");
-                self.get_git_author()
-            })
-            .unwrap_or_else(|| {
-                debug!("No author found anywhere, using fallback");
-                "Developer".to_string()
-            });
+        let author = self.resolve_author(args, &config);
         debug!("Using author: {}", author);
 
         // Get email from config if available
@@ -130,34 +331,96 @@ impl Execute for NewCommand {
 
         if let Some(ref rev) = revision {
             debug!("Using pinned revision: {}", rev);
-            output::info(&format!("📌 Pinned to revision: {}", rev));
+            if !args.json {
+                output::info(&format!("📌 Pinned to revision: {}", rev));
+            }
         } else {
             debug!("Using latest version (no revision pinned)");
         }
 
-        // Create template path with git repository and subfolder
-        let template_path = TemplatePath {
-            git: Some(template_info.repository.clone()),
-            subfolder: Some(template_info.subfolder.clone()),
-            revision,
-            ..Default::default()
-        };
-
-        // Create define arguments for template variables
-        let mut define_args = vec![
-            format!("project_name={}", args.project_name),
-            format!("author={}", author),
-        ];
+        // Collect template variables
+        let mut defines = BTreeMap::from([
+            ("project_name".to_string(), project_name.clone()),
+            ("author".to_string(), author.clone()),
+        ]);
 
         // Add email if available
         if let Some(email_addr) = email {
-            define_args.push(format!("author_email={}", email_addr));
+            defines.insert("author_email".to_string(), email_addr);
         }
 
+        // Add user-provided template values, which may override the author fields
+        defines.extend(self.collect_template_values(args, &config, &template_name)?);
+
+        // Locate the template (through the template cache for remote repositories, except
+        // for dry runs) and the commit the revision points to, for the lock file
+        let progress = (!args.json && !template_info.is_local())
+            .then(|| output::Progress::start("Fetching template..."));
+        let prepare = if args.dry_run {
+            cache::prepare_uncached
+        } else {
+            cache::prepare
+        };
+        let prepared = prepare(&template_info, revision.as_deref(), context::offline())?;
+        if let Some(progress) = progress {
+            progress.finish("Template fetched");
+        }
+        debug!("Resolved template commit: {:?}", prepared.commit);
+        log.line(&format!(
+            "Template {} from {} at {}",
+            template_name,
+            template_info.repository,
+            prepared.commit.as_deref().unwrap_or("an unknown commit")
+        ));
+
+        // The repository manifest can narrow the supported cza versions further
+        let manifest = TemplateManifest::read(&prepared.repository_dir).with_context(|| {
+            format!(
+                "Could not check the cza versions template '{}' supports",
+                template_name
+            )
+        });
+        check(manifest.and_then(|manifest| match manifest {
+            Some(manifest) => self.check_compatibility(
+                &template_name,
+                &manifest.bounds_for(&template_info.subfolder),
+            ),
+            None => Ok(()),
+        }))?;
+
+        // If dry-run, render into a staging directory, show preview and exit
+        if args.dry_run {
+            return self.preview_template(
+                args,
+                &config,
+                &project_name,
+                &template_name,
+                &template_info,
+                revision.as_deref(),
+                prepared,
+                &defines,
+                &failed_checks,
+            );
+        }
+
+        let cache::PreparedTemplate {
+            template_path,
+            commit,
+            ..
+        } = prepared;
+        render::check_values(&template_path, &defines)
+            .map_err(|reason| CzaError::GenerationFailed { reason })?;
+
+        // Create define arguments for template variables
+        let define_args = defines
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+
         // Create cargo-generate args
         let generate_args = GenerateArgs {
             template_path,
-            name: Some(args.project_name.clone()),
+            name: Some(project_name.clone()),
             define: define_args,
             ..Default::default()
         };
@@ -167,96 +430,303 @@ impl Execute for NewCommand {
             "Calling cargo-generate with repository: {}",
             template_info.repository
         );
-        output::step("Generating project from template...");
-        match generate(generate_args) {
+        log.debug("cargo-generate arguments", &generate_args);
+        render::ensure_author(&author);
+        // cargo-generate prompts for placeholders without a value, which the spinner
+        // would draw over
+        let may_prompt = render::may_prompt(&generate_args.template_path, &defines);
+        let progress = output::Progress::start("Generating project from template...");
+        let generated = if may_prompt {
+            debug!("cargo-generate may prompt, hiding the spinner");
+            progress.suspend(|| generate(generate_args))
+        } else {
+            generate(generate_args)
+        };
+        match generated {
             Ok(output_dir) => {
                 info!("Project created successfully at: {}", output_dir.display());
-                output::success("Project created successfully!");
+                log.line(&format!("Project created at {}", output_dir.display()));
+                progress.finish("Project created successfully!");
                 output::directory(&output_dir.display().to_string());
 
-                // Post-generation setup based on config and args
-                self.run_post_generation_setup(&output_dir, &config, args)?;
+                // Record where the project came from
+                let lock = ProjectLock::new(
+                    LockedTemplate {
+                        key: template_name.clone(),
+                        repository: template_info.repository.clone(),
+                        subfolder: template_info.subfolder.clone(),
+                        revision,
+                        commit,
+                    },
+                    defines,
+                );
+                if let Err(e) = lock.write(&output_dir) {
+                    warn!("Could not write lock file: {:#}", e);
+                    output::warning(&format!("Could not write lock file: {}", e));
+                }
+
+                // Post-generation setup based on config and args. A failed step leaves the
+                // project in place, so it's reported after the summary
+                let setup = self.run_post_generation_setup(&output_dir, &config, args, log);
 
-                output::next_steps(&[&format!("cd {}", args.project_name), "mise run dev"]);
+                output::result(&JsonProject {
+                    path: &output_dir,
+                    lock: &lock,
+                });
+                output::next_steps(&[&format!("cd {}", project_name), "mise run dev"]);
+                setup?;
             }
             Err(e) => {
-                return Err(anyhow!("Failed to generate project: {}", e));
+                return Err(CzaError::GenerationFailed {
+                    reason: render::describe_generate_error(&e),
+                }
+                .into());
             }
         }
 
         Ok(())
     }
-}
 
-impl NewCommand {
+    /// Whether missing inputs can be prompted for, which needs a terminal and text output
+    fn can_prompt(&self, args: &NewArgs) -> bool {
+        !args.json && !output::is_json() && wizard::is_interactive()
+    }
+
+    /// Whether inputs are missing that the interactive wizard could ask for
+    fn needs_wizard(&self, args: &NewArgs, config: &Config) -> bool {
+        let missing_template = args.template.is_none()
+            && args.path.is_none()
+            && config.user.default_template.is_none();
+        args.project_name.is_none() || missing_template
+    }
+
+    /// Merge template values from config, the values file and `--define` flags
+    fn collect_template_values(
+        &self,
+        args: &NewArgs,
+        config: &Config,
+        template_name: &str,
+    ) -> Result<template::TemplateValues> {
+        let mut values = template::TemplateValues::new();
+
+        if let Some(table) = config.template_values.get(template_name) {
+            debug!("Using template_values for {} from config", template_name);
+            values.extend(template::table_values(table).with_context(|| {
+                format!("Invalid [template_values.{}] in config", template_name)
+            })?);
+        }
+        if let Some(ref path) = args.values_file {
+            debug!("Reading template values from {}", path.display());
+            values.extend(template::read_values_file(path)?);
+        }
+        values.extend(args.defines.iter().cloned());
+
+        if values.contains_key("project_name") {
+            return Err(anyhow!(
+                "project_name can't be set as a template value, pass it as the project name argument instead"
+            ));
+        }
+        debug!("Collected {} template values", values.len());
+        Ok(values)
+    }
+
+    /// Resolve the author from args, config or git config
+    fn resolve_author(&self, args: &NewArgs, config: &Config) -> String {
+        debug!("Resolving author information");
+        args.author
+            .clone()
+            .or_else(|| {
+                debug!("No author arg provided, checking config");
+                config.user.author.clone()
+            })
+            .or_else(|| {
+                debug!("No author in config, trying git config");
+                self.get_git_author()
+            })
+            .unwrap_or_else(|| {
+                debug!("No author found anywhere, using fallback");
+                render::FALLBACK_AUTHOR.to_string()
+            })
+    }
+
+    /// Resolve the template name from args or config and look it up in the registry
+    fn resolve_registry_template(
+        &self,
+        args: &NewArgs,
+        config: &Config,
+    ) -> Result<(String, template::TemplateInfo)> {
+        let template_name = match &args.template {
+            Some(template) => template.clone(),
+            None => config
+                .user
+                .default_template
+                .clone()
+                .ok_or_else(|| anyhow!("No template specified and no default_template configured. Use 'cza config set user.default_template <template>' to set a default, or specify a template: 'cza new <template> <project_name>'"))?,
+        };
+
+        // Load embedded template registry merged with user templates
+        debug!("Loading template registry");
+        let registry = template::load_registry(config, &args.registries)?;
+
+        // Look up template
+        debug!("Looking up template: {}", template_name);
+        let (key, template_info) = registry.find(&template_name, config.development.autocorrect)?;
+        if *key != template_name && !args.json {
+            output::warning(&format!(
+                "Template '{}' not found, using '{}'",
+                template_name, key
+            ));
+        }
+
+        Ok((key.clone(), template_info.clone()))
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn preview_template(
         &self,
         args: &NewArgs,
+        config: &Config,
+        project_name: &str,
         template_name: &str,
         template_info: &template::TemplateInfo,
+        revision: Option<&str>,
+        prepared: cache::PreparedTemplate,
+        defines: &BTreeMap<String, String>,
+        failed_checks: &[String],
     ) -> Result<()> {
-        output::header("Dry Run Preview");
-        output::info(&format!("Project name: {}", args.project_name));
-        output::info(&format!("Template: {}", template_name));
-        output::info(&format!("Repository: {}", template_info.repository));
-        output::info(&format!("Subfolder: {}", template_info.subfolder));
-
-        // Show revision if pinned (CLI arg takes precedence)
-        let revision = args.revision.as_ref().or(template_info.revision.as_ref());
-        if let Some(rev) = revision {
-            output::info(&format!("📌 Pinned to: {}", rev));
-        } else {
-            output::info("📌 Pinned to: latest");
+        if !args.json {
+            output::header("Dry Run Preview");
+            output::info(&format!("Repository: {}", template_info.repository));
+            if !template_info.subfolder.is_empty() {
+                output::info(&format!("Subfolder: {}", template_info.subfolder));
+            }
+            output::info(&format!("📌 Pinned to: {}", revision.unwrap_or("latest")));
+            if !template_info.frameworks.is_empty() {
+                output::info(&format!(
+                    "Frameworks: {}",
+                    template_info.frameworks.join(", ")
+                ));
+            }
+            output::step("Rendering template into a staging directory...");
         }
 
+        // Render into a throwaway staging directory, removed when dropped
+        let staging = tempfile::TempDir::new().context("Failed to create staging directory")?;
+        let rendered = render::render_template(prepared.template_path, defines, staging.path())?;
+        let tree = render::read_tree(&rendered)?;
+        debug!(
+            "Rendered {} files into {}",
+            tree.len(),
+            staging.path().display()
+        );
+
+        let destination = std::env::current_dir()
+            .context("Failed to determine current directory")?
+            .join(project_name);
+        let plan = self.post_generation_plan(config, args);
+
+        if args.json || output::is_json() {
+            let json_plan = JsonPlan {
+                template: template_name,
+                name: &template_info.name,
+                repository: &template_info.repository,
+                subfolder: &template_info.subfolder,
+                revision,
+                commit: prepared.commit,
+                defines,
+                files: tree
+                    .iter()
+                    .map(|(path, contents)| JsonFile {
+                        path: path.clone(),
+                        size: contents.len() as u64,
+                    })
+                    .collect(),
+                post_generation: plan
+                    .iter()
+                    .map(|step| JsonPostGenStep::new(step, &destination))
+                    .collect(),
+                failed_checks,
+                destination,
+            };
+            return output::print_json(&json_plan);
+        }
+
+        output::step("Files that would be created:");
+        output::info(&format!("  📁 {}/", destination.display()));
+        for line in render::tree_lines(&tree) {
+            output::info(&format!("    {}", line));
+        }
+        let total_size: u64 = tree.values().map(|contents| contents.len() as u64).sum();
         output::info(&format!(
-            "Frameworks: {}",
-            template_info.frameworks.join(", ")
+            "  {} files, {} total",
+            tree.len(),
+            render::format_size(total_size)
         ));
 
-        output::step("What would be created:");
-        output::info(&format!("  📁 ./{}/", args.project_name));
-        output::info("    ├── Cargo.toml (ZK framework dependencies)");
-        output::info("    ├── mise.toml (development tools)");
-        output::info("    ├── package.json (frontend dependencies)");
-        output::info("    ├── src/ (ZK circuit code)");
-        output::info("    └── web/ (frontend application)");
+        output::step("Template variables:");
+        for (key, value) in defines {
+            output::info(&format!("  {} = {}", key, value));
+        }
 
-        output::step("Post-generation setup that would run:");
-        output::info("  1. git init (if enabled in config)");
-        output::info("  2. mise install (if auto_install_deps enabled)");
-        output::info("  3. hk install (if auto_setup_hooks enabled)");
+        output::step("Post-generation steps:");
+        if plan.is_empty() {
+            output::info("  (none)");
+        }
+        for (index, step) in plan.iter().enumerate() {
+            let status = if step.enabled { "run" } else { "skip" };
+            output::info(&format!(
+                "  {}. [{}] {} ({})",
+                index + 1,
+                status,
+                step.action,
+                step.reason
+            ));
+        }
 
-        output::success("Preview complete! Remove --dry-run to create the project.");
+        output::step("Checks:");
+        if failed_checks.is_empty() {
+            output::info("  All checks passed");
+        }
+        for failure in failed_checks {
+            output::warning(failure);
+        }
+
+        if failed_checks.is_empty() {
+            output::success("Preview complete! Remove --dry-run to create the project.");
+        } else {
+            output::warning("Preview complete, but creating the project would fail these checks.");
+        }
 
         Ok(())
     }
 
     fn validate_project_name(&self, name: &str, config: &Config) -> Result<()> {
+        let invalid = |reason| CzaError::InvalidProjectName {
+            name: name.to_string(),
+            reason,
+        };
         if name.is_empty() {
-            return Err(anyhow!("Project name cannot be empty"));
+            return Err(invalid("cannot be empty").into());
         }
 
         // Must start with a letter (consistent with cargo-generate.toml regex)
         if !name.chars().next().unwrap_or('0').is_ascii_alphabetic() {
-            return Err(anyhow!("Project name must start with a letter"));
+            return Err(invalid("must start with a letter").into());
         }
 
         if !name
             .chars()
             .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
         {
-            return Err(anyhow!(
-                "Project name can only contain alphanumeric characters, hyphens, and underscores"
-            ));
+            return Err(invalid(
+                "can only contain alphanumeric characters, hyphens, and underscores",
+            )
+            .into());
         }
 
         if std::path::Path::new(name).exists() {
             if config.development.confirm_overwrite {
-                return Err(anyhow!(
-                    "Directory '{}' already exists. Remove it first or choose a different name.",
-                    name
-                ));
+                return Err(CzaError::DestinationExists { path: name.into() }.into());
             } else {
                 warn!("Directory '{}' already exists but confirm_overwrite is disabled, proceeding anyway", name);
                 output::warning(&format!(
@@ -273,86 +743,196 @@ impl NewCommand {
         utils::get_git_config("user.name")
     }
 
+    /// Post-generation steps for the current config and flags, in execution order
+    fn post_generation_plan(&self, config: &Config, args: &NewArgs) -> Vec<PostGenStep> {
+        post_generation_plan(config, args.no_git)
+    }
+
+    /// Fail when the running cza version is outside the template's supported range
+    fn check_compatibility(&self, template_name: &str, bounds: &VersionBounds) -> Result<()> {
+        let incompatibility = bounds.check().with_context(|| {
+            format!(
+                "Invalid cza version bounds for template '{}'",
+                template_name
+            )
+        })?;
+        match incompatibility {
+            Some(incompatibility) => Err(CzaError::IncompatibleTemplate {
+                template: template_name.to_string(),
+                incompatibility,
+                version: compat::current_version().to_string(),
+            }
+            .into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Fail when tools listed in the template's `requires` table are missing or too old
+    fn check_prerequisites(
+        &self,
+        template_name: &str,
+        template_info: &template::TemplateInfo,
+    ) -> Result<()> {
+        let unmet = template::prereqs::check_prerequisites(&template_info.requires)
+            .with_context(|| format!("Invalid prerequisites for template '{}'", template_name))?;
+        if unmet.is_empty() {
+            return Ok(());
+        }
+
+        Err(CzaError::MissingPrerequisites {
+            template: template_name.to_string(),
+            unmet: unmet.iter().map(ToString::to_string).collect(),
+        }
+        .into())
+    }
+
+    /// Run the enabled post-generation steps
+    ///
+    /// Later steps still run when one fails; the first failure is returned.
     fn run_post_generation_setup(
         &self,
-        output_dir: &std::path::Path,
+        output_dir: &Path,
         config: &Config,
         args: &NewArgs,
+        log: &RunLog,
     ) -> Result<()> {
         debug!("Running post-generation setup");
 
-        // Initialize git if enabled (CLI flag overrides config)
-        let should_init_git = !args.no_git && config.user.git_init;
-        if should_init_git {
-            debug!("git_init is enabled, initializing git repository");
-            let _ = utils::run_post_generation_command(
-                "git",
-                &["init"],
-                output_dir,
-                "Initializing git repository...",
-                "Git repository initialized!",
-                None,
-            );
-        } else if args.no_git {
-            debug!("--no-git flag provided, skipping git initialization");
-        } else {
-            debug!("git_init is disabled in config, skipping git initialization");
-        }
-
-        // Install dependencies if enabled
-        if config.post_generation.auto_install_deps {
-            debug!("auto_install_deps is enabled, running mise install");
-            let _ = utils::run_post_generation_command(
-                "mise",
-                &["install"],
-                output_dir,
-                "Installing dependencies with mise...",
-                "Dependencies installed!",
-                Some("You can run 'mise install' manually in the project directory"),
-            );
-        } else {
-            debug!("auto_install_deps is disabled, skipping dependency installation");
-        }
-
-        // Setup git hooks if enabled (requires git to be initialized)
-        if should_init_git && config.post_generation.auto_setup_hooks {
-            debug!("auto_setup_hooks is enabled and git is initialized, running hk install");
-            let _ = utils::run_post_generation_command(
-                "hk",
-                &["install"],
-                output_dir,
-                "Setting up git hooks with hk...",
-                "Git hooks installed!",
-                Some("You can run 'hk install' manually in the project directory"),
-            );
-        } else if !should_init_git {
-            debug!("git not initialized, skipping git hooks setup");
-        } else {
-            debug!("auto_setup_hooks is disabled, skipping git hooks setup");
-        }
-
-        // Open in editor if configured
-        if let Some(ref editor) = config.post_generation.open_editor {
-            debug!("open_editor is configured: {}, opening project", editor);
-            output::step(&format!("Opening project in {}...", editor));
-
-            let editor_status = std::process::Command::new(editor).arg(output_dir).spawn();
-
-            match editor_status {
-                Ok(_) => {
-                    debug!("Editor opened successfully");
-                    output::success(&format!("Project opened in {}!", editor));
-                }
-                Err(e) => {
-                    warn!("Could not open editor: {}", e);
-                    output::warning(&format!("Could not open {}: {}", editor, e));
+        let mut failure = None;
+        for step in self.post_generation_plan(config, args) {
+            if !step.enabled {
+                debug!("Skipping {}: {}", step.action, step.reason);
+                continue;
+            }
+            debug!("Running {}: {}", step.action, step.reason);
+
+            let result = match step.action {
+                PostGenAction::GitInit => utils::run_post_generation_command(
+                    "git",
+                    &["init"],
+                    output_dir,
+                    "Initializing git repository...",
+                    "Git repository initialized!",
+                    log,
+                ),
+                // mise asks before using a config it doesn't trust yet, which can't be
+                // answered while its output is captured
+                PostGenAction::InstallDeps => utils::run_post_generation_command(
+                    "mise",
+                    &["trust"],
+                    output_dir,
+                    "Trusting the project's mise config...",
+                    "mise config trusted!",
+                    log,
+                )
+                .and_then(|()| {
+                    utils::run_post_generation_command(
+                        "mise",
+                        &["install"],
+                        output_dir,
+                        "Installing dependencies with mise...",
+                        "Dependencies installed!",
+                        log,
+                    )
+                }),
+                PostGenAction::SetupHooks => utils::run_post_generation_command(
+                    "hk",
+                    &["install"],
+                    output_dir,
+                    "Setting up git hooks with hk...",
+                    "Git hooks installed!",
+                    log,
+                ),
+                PostGenAction::OpenEditor(editor) => {
+                    output::step(&format!("Opening project in {}...", editor));
+
+                    match std::process::Command::new(&editor).arg(output_dir).spawn() {
+                        Ok(_) => {
+                            debug!("Editor opened successfully");
+                            output::success(&format!("Project opened in {}!", editor));
+                        }
+                        Err(e) => {
+                            warn!("Could not open editor: {}", e);
+                            output::warning(&format!("Could not open {}: {}", editor, e));
+                        }
+                    }
+                    Ok(())
                 }
+            };
+            if let Err(e) = result {
+                failure.get_or_insert(e);
             }
-        } else {
-            debug!("open_editor is not configured, skipping editor open");
         }
 
-        Ok(())
+        failure.map_or(Ok(()), |e| Err(e.into()))
+    }
+}
+
+/// Post-generation steps for the config and `--no-git` flag, in execution order
+pub(crate) fn post_generation_plan(config: &Config, no_git: bool) -> Vec<PostGenStep> {
+    // Initialize git if enabled (CLI flag overrides config)
+    let should_init_git = !no_git && config.user.git_init;
+    let git_step = if no_git {
+        PostGenStep::new(PostGenAction::GitInit, false, "--no-git flag provided")
+    } else if config.user.git_init {
+        PostGenStep::new(PostGenAction::GitInit, true, "user.git_init is enabled")
+    } else {
+        PostGenStep::new(PostGenAction::GitInit, false, "user.git_init is disabled")
+    };
+
+    let deps_step = if config.post_generation.auto_install_deps {
+        PostGenStep::new(
+            PostGenAction::InstallDeps,
+            true,
+            "post_generation.auto_install_deps is enabled",
+        )
+    } else {
+        PostGenStep::new(
+            PostGenAction::InstallDeps,
+            false,
+            "post_generation.auto_install_deps is disabled",
+        )
+    };
+
+    // Git hooks require git to be initialized
+    let hooks_step = if !config.post_generation.auto_setup_hooks {
+        PostGenStep::new(
+            PostGenAction::SetupHooks,
+            false,
+            "post_generation.auto_setup_hooks is disabled",
+        )
+    } else if !should_init_git {
+        PostGenStep::new(PostGenAction::SetupHooks, false, "git is not initialized")
+    } else {
+        PostGenStep::new(
+            PostGenAction::SetupHooks,
+            true,
+            "post_generation.auto_setup_hooks is enabled",
+        )
+    };
+
+    let mut plan = vec![git_step, deps_step, hooks_step];
+    if let Some(ref editor) = config.post_generation.open_editor {
+        plan.push(PostGenStep::new(
+            PostGenAction::OpenEditor(editor.clone()),
+            true,
+            "post_generation.open_editor is set",
+        ));
+    }
+    plan
+}
+
+/// Usage error for a missing project name, as when it was a required argument
+fn missing_project_name() -> CzaError {
+    let mut cli = Cli::command();
+    cli.build();
+    let usage = cli
+        .find_subcommand_mut("new")
+        .map(|new| new.render_usage().to_string())
+        .unwrap_or_default();
+    CzaError::MissingArgument {
+        argument: "<PROJECT_NAME>".to_string(),
+        usage,
     }
 }
 
@@ -463,32 +1043,70 @@ frameworks = ["test"]
     fn test_new_command_invalid_template() {
         let cmd = NewCommand;
         let args = NewArgs {
-            project_name: "test-project".to_string(),
+            project_name: Some("test-project".to_string()),
             template: Some("nonexistent-template".to_string()),
             author: None,
+            defines: vec![],
+            values_file: None,
             no_git: false,
+            skip_prereq_check: false,
             dry_run: false,
+            json: false,
             revision: None,
+            path: None,
+            registries: vec![],
         };
 
-        let result = cmd.run(&args);
+        let result = cmd.create(&args, &RunLog::disabled());
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
 
+    #[test]
+    fn test_new_command_missing_project_name() {
+        let args = NewArgs {
+            project_name: None,
+            template: Some("noir-vite".to_string()),
+            author: None,
+            defines: vec![],
+            values_file: None,
+            no_git: false,
+            skip_prereq_check: false,
+            dry_run: true,
+            json: true,
+            revision: None,
+            path: None,
+            registries: vec![],
+        };
+
+        // Without a terminal to prompt in, this is a usage error
+        let err = NewCommand.run(&args).unwrap_err();
+        assert_eq!(crate::error::exit_code(&err), 2);
+        let report = crate::error::ErrorReport::new(&err);
+        assert_eq!(report.code, "missing_argument");
+        assert!(report.message.contains("<PROJECT_NAME>"));
+        assert!(report.hint.unwrap().starts_with("Usage: cza new"));
+    }
+
     #[test]
     fn test_new_command_invalid_project_name() {
         let cmd = NewCommand;
         let args = NewArgs {
-            project_name: "invalid name".to_string(),
+            project_name: Some("invalid name".to_string()),
             template: Some("noir-vite".to_string()),
             author: None,
+            defines: vec![],
+            values_file: None,
             no_git: false,
+            skip_prereq_check: false,
             dry_run: false,
+            json: false,
             revision: None,
+            path: None,
+            registries: vec![],
         };
 
-        let result = cmd.run(&args);
+        let result = cmd.create(&args, &RunLog::disabled());
         assert!(result.is_err());
     }
 
@@ -496,16 +1114,22 @@ frameworks = ["test"]
     fn test_new_command_with_author() {
         let cmd = NewCommand;
         let args = NewArgs {
-            project_name: "test-project".to_string(),
+            project_name: Some("test-project".to_string()),
             template: Some("nonexistent-template".to_string()),
             author: Some("Test Author".to_string()),
+            defines: vec![],
+            values_file: None,
             no_git: false,
+            skip_prereq_check: false,
             dry_run: false,
+            json: false,
             revision: None,
+            path: None,
+            registries: vec![],
         };
 
         // This will fail on template lookup, but we can still test author handling
-        let result = cmd.run(&args);
+        let result = cmd.create(&args, &RunLog::disabled());
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -559,16 +1183,22 @@ frameworks = ["test"]
         let author = Some("Test Author".to_string());
 
         let args = NewArgs {
-            project_name: name.clone(),
+            project_name: Some(name.clone()),
             template: template.clone(),
             author: author.clone(),
+            defines: vec![],
+            values_file: None,
             no_git: false,
+            skip_prereq_check: false,
             dry_run: false,
+            json: false,
             revision: None,
+            path: None,
+            registries: vec![],
         };
 
         assert_eq!(args.template, template);
-        assert_eq!(args.project_name, name);
+        assert_eq!(args.project_name, Some(name));
         assert_eq!(args.author, author);
     }
 
@@ -576,16 +1206,22 @@ frameworks = ["test"]
     fn test_default_template_config_integration() {
         // Test that NewArgs can use None for template to rely on config
         let args = NewArgs {
-            project_name: "test-project".to_string(),
+            project_name: Some("test-project".to_string()),
             template: None,
             author: None,
+            defines: vec![],
+            values_file: None,
             no_git: false,
+            skip_prereq_check: false,
             dry_run: false,
+            json: false,
             revision: None,
+            path: None,
+            registries: vec![],
         };
 
         assert_eq!(args.template, None);
-        assert_eq!(args.project_name, "test-project");
+        assert_eq!(args.project_name, Some("test-project".to_string()));
         assert_eq!(args.author, None);
         assert!(!args.no_git);
         assert!(!args.dry_run);
@@ -616,16 +1252,22 @@ frameworks = ["test"]
         // Test that CLI arg author takes precedence over config
         let cmd = NewCommand;
         let args = NewArgs {
-            project_name: "test-project".to_string(),
+            project_name: Some("test-project".to_string()),
             template: Some("nonexistent-template".to_string()),
             author: Some("CLI Author".to_string()),
+            defines: vec![],
+            values_file: None,
             no_git: false,
+            skip_prereq_check: false,
             dry_run: false,
+            json: false,
             revision: None,
+            path: None,
+            registries: vec![],
         };
 
         // Even though this will fail on template lookup, we can verify the precedence logic exists
-        let result = cmd.run(&args);
+        let result = cmd.create(&args, &RunLog::disabled());
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -637,20 +1279,29 @@ frameworks = ["test"]
         let cmd = NewCommand;
         let mut config = Config::default();
         config.user.git_init = true;
+        // Only git is under test, mise and hk may not be installed
+        config.post_generation.auto_install_deps = false;
+        config.post_generation.auto_setup_hooks = false;
 
         let args = NewArgs {
-            project_name: "test-project".to_string(),
+            project_name: Some("test-project".to_string()),
             template: None,
             author: None,
+            defines: vec![],
+            values_file: None,
             no_git: true,
+            skip_prereq_check: false,
             dry_run: false,
+            json: false,
             revision: None,
+            path: None,
+            registries: vec![],
         };
 
         let temp_dir = TempDir::new().unwrap();
         let temp_path = temp_dir.path();
 
-        let result = cmd.run_post_generation_setup(temp_path, &config, &args);
+        let result = cmd.run_post_generation_setup(temp_path, &config, &args, &RunLog::disabled());
         assert!(result.is_ok());
 
         assert!(!temp_path.join(".git").exists());
@@ -663,20 +1314,29 @@ frameworks = ["test"]
         let cmd = NewCommand;
         let mut config = Config::default();
         config.user.git_init = true;
+        // Only git is under test, mise and hk may not be installed
+        config.post_generation.auto_install_deps = false;
+        config.post_generation.auto_setup_hooks = false;
 
         let args = NewArgs {
-            project_name: "test-project".to_string(),
+            project_name: Some("test-project".to_string()),
             template: None,
             author: None,
+            defines: vec![],
+            values_file: None,
             no_git: false,
+            skip_prereq_check: false,
             dry_run: false,
+            json: false,
             revision: None,
+            path: None,
+            registries: vec![],
         };
 
         let temp_dir = TempDir::new().unwrap();
         let temp_path = temp_dir.path();
 
-        let result = cmd.run_post_generation_setup(temp_path, &config, &args);
+        let result = cmd.run_post_generation_setup(temp_path, &config, &args, &RunLog::disabled());
         assert!(result.is_ok());
 
         assert!(temp_path.join(".git").exists());
@@ -689,20 +1349,29 @@ frameworks = ["test"]
         let cmd = NewCommand;
         let mut config = Config::default();
         config.user.git_init = false;
+        // Only git is under test, mise and hk may not be installed
+        config.post_generation.auto_install_deps = false;
+        config.post_generation.auto_setup_hooks = false;
 
         let args = NewArgs {
-            project_name: "test-project".to_string(),
+            project_name: Some("test-project".to_string()),
             template: None,
             author: None,
+            defines: vec![],
+            values_file: None,
             no_git: false,
+            skip_prereq_check: false,
             dry_run: false,
+            json: false,
             revision: None,
+            path: None,
+            registries: vec![],
         };
 
         let temp_dir = TempDir::new().unwrap();
         let temp_path = temp_dir.path();
 
-        let result = cmd.run_post_generation_setup(temp_path, &config, &args);
+        let result = cmd.run_post_generation_setup(temp_path, &config, &args, &RunLog::disabled());
         assert!(result.is_ok());
 
         assert!(!temp_path.join(".git").exists());
@@ -710,30 +1379,47 @@ frameworks = ["test"]
 
     #[test]
     fn test_dry_run_flag() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("README.md"), "# {{project_name}}\n").unwrap();
+
         let cmd = NewCommand;
         let args = NewArgs {
-            project_name: "test-project".to_string(),
-            template: Some("noir-vite".to_string()),
+            project_name: Some("dry-run-project".to_string()),
+            template: None,
             author: None,
+            defines: vec![],
+            values_file: None,
             no_git: false,
+            skip_prereq_check: false,
             dry_run: true,
+            json: false,
             revision: None,
+            path: Some(temp_dir.path().to_path_buf()),
+            registries: vec![],
         };
 
         let result = cmd.run(&args);
         assert!(result.is_ok());
-        assert!(!std::path::Path::new("test-project").exists());
+        assert!(!std::path::Path::new("dry-run-project").exists());
     }
 
     #[test]
     fn test_dry_run_flag_false() {
         let args = NewArgs {
-            project_name: "test-project".to_string(),
+            project_name: Some("test-project".to_string()),
             template: Some("noir-vite".to_string()),
             author: None,
+            defines: vec![],
+            values_file: None,
             no_git: false,
+            skip_prereq_check: false,
             dry_run: false,
+            json: false,
             revision: None,
+            path: None,
+            registries: vec![],
         };
 
         assert!(!args.dry_run);
@@ -742,12 +1428,18 @@ frameworks = ["test"]
     #[test]
     fn test_revision_flag() {
         let args = NewArgs {
-            project_name: "test-project".to_string(),
+            project_name: Some("test-project".to_string()),
             template: Some("noir-vite".to_string()),
             author: None,
+            defines: vec![],
+            values_file: None,
             no_git: false,
+            skip_prereq_check: false,
             dry_run: false,
+            json: false,
             revision: Some("abc123def".to_string()),
+            path: None,
+            registries: vec![],
         };
 
         assert_eq!(args.revision, Some("abc123def".to_string()));
@@ -756,14 +1448,138 @@ frameworks = ["test"]
     #[test]
     fn test_revision_flag_none() {
         let args = NewArgs {
-            project_name: "test-project".to_string(),
+            project_name: Some("test-project".to_string()),
             template: Some("noir-vite".to_string()),
             author: None,
+            defines: vec![],
+            values_file: None,
             no_git: false,
+            skip_prereq_check: false,
             dry_run: false,
+            json: false,
             revision: None,
+            path: None,
+            registries: vec![],
         };
 
         assert_eq!(args.revision, None);
     }
+
+    #[test]
+    fn test_dry_run_with_local_path() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("main.nr"), "fn main() {}\n").unwrap();
+        let cmd = NewCommand;
+        let args = NewArgs {
+            project_name: Some("local-dry-run".to_string()),
+            template: None,
+            author: None,
+            defines: vec![],
+            values_file: None,
+            no_git: false,
+            skip_prereq_check: false,
+            dry_run: true,
+            json: false,
+            revision: None,
+            path: Some(temp_dir.path().to_path_buf()),
+            registries: vec![],
+        };
+
+        assert!(cmd.run(&args).is_ok());
+    }
+
+    #[test]
+    fn test_local_path_missing_directory() {
+        let cmd = NewCommand;
+        let args = NewArgs {
+            project_name: Some("test-project".to_string()),
+            template: None,
+            author: None,
+            defines: vec![],
+            values_file: None,
+            no_git: false,
+            skip_prereq_check: false,
+            dry_run: true,
+            json: false,
+            revision: None,
+            path: Some(PathBuf::from("/nonexistent/template/dir")),
+            registries: vec![],
+        };
+
+        let result = cmd.run(&args);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not found"));
+    }
+
+    fn plan_args(no_git: bool) -> NewArgs {
+        NewArgs {
+            project_name: Some("test-project".to_string()),
+            template: None,
+            author: None,
+            defines: vec![],
+            values_file: None,
+            no_git,
+            skip_prereq_check: false,
+            dry_run: true,
+            json: false,
+            revision: None,
+            path: None,
+            registries: vec![],
+        }
+    }
+
+    #[test]
+    fn test_post_generation_plan_defaults() {
+        let cmd = NewCommand;
+        let config = Config::default();
+
+        let plan = cmd.post_generation_plan(&config, &plan_args(false));
+        let actions: Vec<_> = plan.iter().map(|step| step.action.clone()).collect();
+        assert_eq!(
+            actions,
+            vec![
+                PostGenAction::GitInit,
+                PostGenAction::InstallDeps,
+                PostGenAction::SetupHooks,
+            ]
+        );
+        assert!(plan.iter().all(|step| step.enabled));
+    }
+
+    #[test]
+    fn test_post_generation_plan_no_git_skips_hooks() {
+        let cmd = NewCommand;
+        let mut config = Config::default();
+        config.post_generation.auto_install_deps = false;
+        config.post_generation.open_editor = Some("code".to_string());
+
+        let plan = cmd.post_generation_plan(&config, &plan_args(true));
+        assert!(!plan[0].enabled);
+        assert_eq!(plan[0].reason, "--no-git flag provided");
+        assert!(!plan[1].enabled);
+        assert!(!plan[2].enabled);
+        assert_eq!(plan[2].reason, "git is not initialized");
+        assert_eq!(
+            plan[3].action,
+            PostGenAction::OpenEditor("code".to_string())
+        );
+        assert_eq!(plan[3].action.to_string(), "code <project>");
+        assert!(plan[3].enabled);
+    }
+
+    #[test]
+    fn test_post_generation_commands() {
+        let project_dir = Path::new("/tmp/my-app");
+        assert_eq!(PostGenAction::GitInit.id(), "git_init");
+        assert_eq!(
+            PostGenAction::InstallDeps.command(project_dir),
+            vec!["mise", "install"]
+        );
+        assert_eq!(
+            PostGenAction::OpenEditor("code".to_string()).command(project_dir),
+            vec!["code", "/tmp/my-app"]
+        );
+    }
 }
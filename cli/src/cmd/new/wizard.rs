@@ -0,0 +1,258 @@
+//! Interactive project wizard for `cza new`
+//!
+//! When `cza new` runs in a terminal without a project name, or without a template and no
+//! `user.default_template` configured, the wizard prompts for:
+//!
+//! - The template, picked from the registry with its description and frameworks
+//! - The project name, validated with the same rules as the non-interactive path
+//! - The author and email
+//! - Which post-generation steps to run (git init, mise install, hk install)
+//!
+//! Non-interactive runs never prompt and keep failing on missing inputs.
+
+use super::{NewArgs, PostGenAction};
+use crate::{
+    config::Config,
+    output,
+    template::{self, TemplateRegistry},
+};
+use anyhow::{anyhow, Context, Result};
+use dialoguer::{theme::ColorfulTheme, Input, MultiSelect, Select};
+use log::debug;
+use std::io::IsTerminal;
+
+/// Whether both stdin and stdout are attached to a terminal
+pub fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+/// Answers collected by the wizard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    /// Registry template key, when the template was picked in the wizard
+    pub template: Option<String>,
+    pub project_name: String,
+    pub author: String,
+    pub email: Option<String>,
+    pub git_init: bool,
+    pub install_deps: bool,
+    pub setup_hooks: bool,
+}
+
+impl Answers {
+    /// Fold the answers into the arguments and configuration used for this run
+    pub fn apply(self, args: &mut NewArgs, config: &mut Config) {
+        if self.template.is_some() {
+            args.template = self.template;
+        }
+        args.project_name = Some(self.project_name);
+        args.author = Some(self.author);
+        // The wizard answer replaces --no-git
+        args.no_git = false;
+        config.user.email = self.email;
+        config.user.git_init = self.git_init;
+        config.post_generation.auto_install_deps = self.install_deps;
+        config.post_generation.auto_setup_hooks = self.setup_hooks;
+    }
+}
+
+/// Prompt for the inputs `cza new` needs
+///
+/// `validate_name` checks project names as they are typed and `default_author` is
+/// pre-filled in the author prompt.
+pub fn prompt(
+    args: &NewArgs,
+    config: &Config,
+    validate_name: impl Fn(&str) -> Result<()>,
+    default_author: String,
+) -> Result<Answers> {
+    let theme = ColorfulTheme::default();
+    output::header("Create a new ZK application");
+
+    // Only ask for a template when none was given
+    let template = if args.template.is_none() && args.path.is_none() {
+        let registry = template::load_registry(config, &args.registries)?;
+        let items = template_items(&registry);
+        if items.is_empty() {
+            return Err(anyhow!("No templates available in the registry"));
+        }
+        let labels: Vec<&str> = items.iter().map(|(_, label)| label.as_str()).collect();
+        let default = config
+            .user
+            .default_template
+            .as_ref()
+            .and_then(|default| items.iter().position(|(key, _)| key == default))
+            .unwrap_or(0);
+
+        let index = Select::with_theme(&theme)
+            .with_prompt("Template")
+            .items(&labels)
+            .default(default)
+            .interact()
+            .context("Failed to read template selection")?;
+        Some(items[index].0.clone())
+    } else {
+        None
+    };
+    debug!("Wizard template: {:?}", template);
+
+    let mut name_input = Input::<String>::with_theme(&theme).with_prompt("Project name");
+    if let Some(ref name) = args.project_name {
+        name_input = name_input.default(name.clone());
+    }
+    let project_name = name_input
+        .validate_with(|name: &String| validate_name(name).map_err(|e| e.to_string()))
+        .interact_text()
+        .context("Failed to read project name")?;
+
+    let author = Input::<String>::with_theme(&theme)
+        .with_prompt("Author")
+        .default(default_author)
+        .interact_text()
+        .context("Failed to read author")?;
+
+    let email = Input::<String>::with_theme(&theme)
+        .with_prompt("Email (optional)")
+        .default(config.user.email.clone().unwrap_or_default())
+        .allow_empty(true)
+        .interact_text()
+        .context("Failed to read email")?;
+
+    let steps = [
+        PostGenAction::GitInit.to_string(),
+        PostGenAction::InstallDeps.to_string(),
+        PostGenAction::SetupHooks.to_string(),
+    ];
+    let defaults = [
+        !args.no_git && config.user.git_init,
+        config.post_generation.auto_install_deps,
+        config.post_generation.auto_setup_hooks,
+    ];
+    let selected = MultiSelect::with_theme(&theme)
+        .with_prompt("Setup steps (space to toggle)")
+        .items(&steps)
+        .defaults(&defaults)
+        .interact()
+        .context("Failed to read setup steps")?;
+
+    Ok(Answers {
+        template,
+        project_name,
+        author,
+        email: Some(email.trim().to_string()).filter(|email| !email.is_empty()),
+        git_init: selected.contains(&0),
+        install_deps: selected.contains(&1),
+        setup_hooks: selected.contains(&2),
+    })
+}
+
+/// Selection entries for the registry templates, sorted by key
+fn template_items(registry: &TemplateRegistry) -> Vec<(String, String)> {
+    let mut templates: Vec<_> = registry.templates.iter().collect();
+    templates.sort_by_key(|(key, _)| *key);
+    templates
+        .into_iter()
+        .map(|(key, info)| {
+            let label = if info.frameworks.is_empty() {
+                format!("{} - {}", key, info.description)
+            } else {
+                format!(
+                    "{} - {} [{}]",
+                    key,
+                    info.description,
+                    info.frameworks.join(", ")
+                )
+            };
+            (key.clone(), label)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers() -> Answers {
+        Answers {
+            template: Some("cairo-vite".to_string()),
+            project_name: "my-app".to_string(),
+            author: "Jane Doe".to_string(),
+            email: None,
+            git_init: false,
+            install_deps: true,
+            setup_hooks: false,
+        }
+    }
+
+    #[test]
+    fn test_template_items() {
+        let registry = template::load_template_registry().unwrap();
+        let items = template_items(&registry);
+
+        let keys: Vec<_> = items.iter().map(|(key, _)| key.as_str()).collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+
+        let (_, label) = items.iter().find(|(key, _)| key == "noir-vite").unwrap();
+        assert!(label.starts_with("noir-vite - "));
+        assert!(label.contains("noir"));
+    }
+
+    #[test]
+    fn test_apply_answers() {
+        let mut args = NewArgs {
+            project_name: None,
+            template: None,
+            author: None,
+            defines: vec![],
+            values_file: None,
+            no_git: false,
+            skip_prereq_check: false,
+            dry_run: false,
+            json: false,
+            revision: None,
+            path: None,
+            registries: vec![],
+        };
+        let mut config = Config::default();
+        config.user.email = Some("old@example.com".to_string());
+
+        answers().apply(&mut args, &mut config);
+
+        assert_eq!(args.template, Some("cairo-vite".to_string()));
+        assert_eq!(args.project_name, Some("my-app".to_string()));
+        assert_eq!(args.author, Some("Jane Doe".to_string()));
+        assert!(!args.no_git);
+        assert_eq!(config.user.email, None);
+        assert!(!config.user.git_init);
+        assert!(config.post_generation.auto_install_deps);
+        assert!(!config.post_generation.auto_setup_hooks);
+    }
+
+    #[test]
+    fn test_apply_answers_keeps_given_template() {
+        let mut args = NewArgs {
+            project_name: None,
+            template: Some("noir-vite".to_string()),
+            author: None,
+            defines: vec![],
+            values_file: None,
+            no_git: false,
+            skip_prereq_check: false,
+            dry_run: false,
+            json: false,
+            revision: None,
+            path: None,
+            registries: vec![],
+        };
+        let mut config = Config::default();
+
+        Answers {
+            template: None,
+            ..answers()
+        }
+        .apply(&mut args, &mut config);
+        assert_eq!(args.template, Some("noir-vite".to_string()));
+    }
+}
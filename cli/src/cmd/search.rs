@@ -0,0 +1,171 @@
+use super::{
+    list::{self, SortOrder},
+    Execute,
+};
+use crate::{
+    context, output,
+    template::{self, TemplateInfo},
+};
+use anyhow::Result;
+use clap::Args;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use log::debug;
+
+#[derive(Args, Debug)]
+pub struct SearchArgs {
+    /// Words to look for in template keys, names, descriptions and frameworks
+    #[arg(required = true, num_args = 1..)]
+    query: Vec<String>,
+
+    /// Show detailed information about matching templates
+    #[arg(long)]
+    detailed: bool,
+
+    /// Output matching templates as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Only show templates including this framework (repeatable, all must match)
+    #[arg(long = "framework", value_name = "FRAMEWORK")]
+    frameworks: Vec<String>,
+
+    /// Order in which matches are shown
+    #[arg(long, value_enum, default_value_t = SortOrder::Relevance)]
+    sort: SortOrder,
+
+    /// Additional registry to include, as a URL or file path (optionally NAME=LOCATION)
+    #[arg(long = "registry", value_name = "[NAME=]LOCATION")]
+    registries: Vec<template::RegistrySpec>,
+}
+
+pub struct SearchCommand;
+
+impl Execute for SearchCommand {
+    type Args = SearchArgs;
+
+    fn json_output(&self, args: &Self::Args) -> bool {
+        args.json || output::is_json()
+    }
+
+    fn run(&self, args: &Self::Args) -> Result<()> {
+        let query = args.query.join(" ");
+        debug!("Searching templates for '{}'", query);
+
+        let config = context::config()?;
+        let registry = template::load_registry(&config, &args.registries)?;
+
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let mut scored: Vec<_> = registry
+            .templates
+            .iter()
+            .filter(|(_, info)| list::has_frameworks(info, &args.frameworks))
+            .filter_map(|(key, info)| {
+                score(&matcher, &query, key, info).map(|score| (score, key, info))
+            })
+            .collect();
+        // Best matches first, ties broken by key
+        scored.sort_by(|(a_score, a_key, _), (b_score, b_key, _)| {
+            b_score.cmp(a_score).then_with(|| a_key.cmp(b_key))
+        });
+        debug!("Found {} matching templates", scored.len());
+
+        let mut templates: Vec<_> = scored
+            .into_iter()
+            .map(|(_, key, info)| (key, info))
+            .collect();
+        list::sort_templates(&mut templates, args.sort);
+
+        if self.json_output(args) {
+            return list::print_json(&templates);
+        }
+
+        if templates.is_empty() {
+            output::warning(&format!("No templates match '{}'", query));
+            output::info("Use 'cza list' to see all available templates.");
+            return Ok(());
+        }
+
+        output::header(&format!("Templates matching '{}'", query));
+        list::print_templates(&templates, args.detailed);
+        if !args.detailed {
+            output::info("Use --detailed for more information about these templates.");
+        }
+        Ok(())
+    }
+}
+
+/// Fuzzy match score of a template for `query`, `None` when a word doesn't match at all
+///
+/// Each whitespace-separated word is matched against the key, name, description and
+/// frameworks, keeping its best score. Matches on the key, name and frameworks count
+/// twice as much as matches in the description.
+fn score(matcher: &SkimMatcherV2, query: &str, key: &str, info: &TemplateInfo) -> Option<i64> {
+    query
+        .split_whitespace()
+        .map(|word| {
+            let strong = [key, info.name.as_str()]
+                .into_iter()
+                .chain(info.frameworks.iter().map(String::as_str))
+                .filter_map(|text| matcher.fuzzy_match(text, word))
+                .map(|score| score * 2);
+            let weak = matcher.fuzzy_match(&info.description, word);
+            strong.chain(weak).max()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::TemplateSource;
+
+    fn info(name: &str, description: &str, frameworks: &[&str]) -> TemplateInfo {
+        TemplateInfo {
+            name: name.to_string(),
+            description: description.to_string(),
+            repository: "https://github.com/test/test".to_string(),
+            subfolder: String::new(),
+            frameworks: frameworks.iter().map(|f| f.to_string()).collect(),
+            revision: None,
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source: TemplateSource::Builtin,
+        }
+    }
+
+    #[test]
+    fn test_score() {
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let noir = info(
+            "Noir + Vite",
+            "Zero-knowledge app with Noir",
+            &["noir", "react"],
+        );
+        let cairo = info("Cairo + Vite", "StarkNet app with Cairo", &["cairo"]);
+
+        assert!(score(&matcher, "noir", "noir-vite", &noir).is_some());
+        assert!(score(&matcher, "noir", "cairo-vite", &cairo).is_none());
+        // Typos still match as long as the letters appear in order
+        assert!(score(&matcher, "nir", "noir-vite", &noir).is_some());
+        // Every word must match
+        assert!(score(&matcher, "noir react", "noir-vite", &noir).is_some());
+        assert!(score(&matcher, "noir cairo", "noir-vite", &noir).is_none());
+        // Matches on names rank above matches in descriptions only
+        let starknet = score(&matcher, "starknet", "cairo-vite", &cairo).unwrap();
+        let cairo_score = score(&matcher, "cairo", "cairo-vite", &cairo).unwrap();
+        assert!(cairo_score > starknet);
+    }
+
+    #[test]
+    fn test_search_command_execute() {
+        let args = SearchArgs {
+            query: vec!["noir".to_string()],
+            detailed: false,
+            json: true,
+            frameworks: vec![],
+            sort: SortOrder::Relevance,
+            registries: vec![],
+        };
+        assert!(SearchCommand.run(&args).is_ok());
+    }
+}
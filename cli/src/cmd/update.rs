@@ -4,6 +4,7 @@ use anyhow::{Context, Result};
 use clap::Args;
 use log::debug;
 use self_update::cargo_crate_version;
+use serde_json::json;
 
 #[derive(Args, Debug)]
 pub struct UpdateArgs;
@@ -39,6 +40,7 @@ impl Execute for UpdateCommand {
         // Compare versions
         if current_version == latest_version {
             output::success(&format!("Already up to date (v{})", current_version));
+            output::result(&json!({ "updated": false, "version": current_version }));
             return Ok(());
         }
 
@@ -53,7 +55,7 @@ impl Execute for UpdateCommand {
             .repo_owner("sripwoud")
             .repo_name("cza")
             .bin_name("cza")
-            .show_download_progress(true)
+            .show_download_progress(!output::is_json())
             .current_version(current_version)
             .build()
             .context("Failed to configure updater")?
@@ -64,12 +66,14 @@ impl Execute for UpdateCommand {
             Ok(update_status) => match update_status {
                 self_update::Status::UpToDate(version) => {
                     output::success(&format!("Already up to date (v{})", version));
+                    output::result(&json!({ "updated": false, "version": version }));
                 }
                 self_update::Status::Updated(version) => {
                     output::success(&format!("Successfully updated to v{}", version));
                     output::info(
                         "Restart your terminal or run 'cza --version' to verify the update",
                     );
+                    output::result(&json!({ "updated": true, "version": version }));
                 }
             },
             Err(e) => {
@@ -0,0 +1,526 @@
+use super::Execute;
+use crate::{
+    lock::{ProjectLock, LOCK_FILE_NAME},
+    output,
+    render::{self, FileTree},
+    template,
+};
+use anyhow::{anyhow, Context, Result};
+use clap::Args;
+use log::debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Args, Debug)]
+pub struct UpgradeArgs {
+    /// Project directory to upgrade (defaults to the current directory)
+    project_dir: Option<PathBuf>,
+
+    /// Template revision to upgrade to (commit SHA, tag, or branch). Defaults to the latest
+    #[arg(long)]
+    revision: Option<String>,
+
+    /// Show the changes as a patch without modifying any files
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Change to apply to a single project file
+#[derive(Debug, PartialEq)]
+enum Change {
+    /// Write new contents, containing conflict markers when `conflicted`
+    Write {
+        path: PathBuf,
+        contents: Vec<u8>,
+        conflicted: bool,
+    },
+    /// Remove a file the template no longer produces
+    Delete { path: PathBuf },
+    /// Template change that can't be applied, saved as `<path>.rej`
+    Reject { path: PathBuf, patch: String },
+}
+
+pub struct UpgradeCommand;
+
+impl Execute for UpgradeCommand {
+    type Args = UpgradeArgs;
+
+    fn run(&self, args: &Self::Args) -> Result<()> {
+        let project_dir = args
+            .project_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("."));
+        debug!("Starting upgrade command in {}", project_dir.display());
+
+        let mut lock = ProjectLock::read(&project_dir)?;
+        let old_revision = lock
+            .template
+            .locked_revision()
+            .map(str::to_string)
+            .ok_or_else(|| {
+                anyhow!(
+                    "{} does not record the template revision the project was generated from",
+                    LOCK_FILE_NAME
+                )
+            })?;
+        let template_info = lock.template.template_info();
+
+        output::step(&format!(
+            "Upgrading project generated from template: {}",
+            lock.template.key
+        ));
+
+        // Resolve the target revision: CLI arg or latest
+        let new_commit = template::resolve_commit(&template_info, args.revision.as_deref());
+        let new_revision = new_commit
+            .clone()
+            .or_else(|| args.revision.clone())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Could not resolve the latest revision of {}. Use --revision to pick one.",
+                    template_info.repository
+                )
+            })?;
+
+        if new_revision == old_revision {
+            output::success(&format!(
+                "Project is already up to date with revision {}",
+                old_revision
+            ));
+            return Ok(());
+        }
+        output::info(&format!("📌 {} → {}", old_revision, new_revision));
+
+        // Render both revisions with the project's original values
+        let scratch = tempfile::TempDir::new().context("Failed to create temporary directory")?;
+        output::step("Rendering the original template revision...");
+        let base = render::read_tree(&render::render_revision(
+            &template_info,
+            Some(&old_revision),
+            &lock.defines,
+            &scratch.path().join("base"),
+        )?)?;
+        output::step("Rendering the new template revision...");
+        let new = render::read_tree(&render::render_revision(
+            &template_info,
+            Some(&new_revision),
+            &lock.defines,
+            &scratch.path().join("new"),
+        )?)?;
+
+        let changes = self.plan_changes(&base, &new, &project_dir)?;
+
+        if args.dry_run {
+            self.print_patch(&changes, &project_dir);
+            output::success("Dry run complete! Remove --dry-run to apply the upgrade.");
+            return Ok(());
+        }
+
+        self.apply_changes(&changes, &project_dir)?;
+
+        // Record the new revision
+        let mut template = lock.template.clone();
+        template.revision = args.revision.clone();
+        template.commit = new_commit;
+        lock.upgrade(template);
+        lock.write(&project_dir)?;
+
+        self.print_summary(&changes);
+        Ok(())
+    }
+}
+
+impl UpgradeCommand {
+    /// Work out how each file changed between template revisions applies to the project
+    fn plan_changes(
+        &self,
+        base: &FileTree,
+        new: &FileTree,
+        project_dir: &Path,
+    ) -> Result<Vec<Change>> {
+        let mut changes = Vec::new();
+
+        for path in render::union_paths(base, new) {
+            let base_contents = base.get(path).map(Vec::as_slice);
+            let new_contents = new.get(path).map(Vec::as_slice);
+            if base_contents == new_contents {
+                continue;
+            }
+
+            let ours = fs::read(project_dir.join(path)).ok();
+            let ours = ours.as_deref();
+            let path = path.clone();
+
+            let change = match (base_contents, new_contents, ours) {
+                // Project already matches the new template
+                (_, new_contents, ours) if ours == new_contents => continue,
+                // Added by the template
+                (None, Some(new_contents), None) => Change::Write {
+                    path,
+                    contents: new_contents.to_vec(),
+                    conflicted: false,
+                },
+                // Unchanged in the project: take the template's version
+                (Some(_), Some(new_contents), ours) if ours == base_contents => Change::Write {
+                    path,
+                    contents: new_contents.to_vec(),
+                    conflicted: false,
+                },
+                (Some(_), None, ours) if ours == base_contents => Change::Delete { path },
+                // Changed on both sides: three-way merge
+                (base_contents, Some(new_contents), Some(ours)) => {
+                    match merge_file(base_contents.unwrap_or_default(), ours, new_contents)? {
+                        Some((contents, conflicted)) => Change::Write {
+                            path,
+                            contents,
+                            conflicted,
+                        },
+                        None => Change::Reject {
+                            patch: render::unified_diff(&path, base_contents, Some(new_contents)),
+                            path,
+                        },
+                    }
+                }
+                // Deleted on one side, modified on the other
+                (base_contents, new_contents, _) => Change::Reject {
+                    patch: render::unified_diff(&path, base_contents, new_contents),
+                    path,
+                },
+            };
+            changes.push(change);
+        }
+
+        Ok(changes)
+    }
+
+    fn apply_changes(&self, changes: &[Change], project_dir: &Path) -> Result<()> {
+        for change in changes {
+            match change {
+                Change::Write { path, contents, .. } => {
+                    let target = project_dir.join(path);
+                    if let Some(parent) = target.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(&target, contents)
+                        .with_context(|| format!("Failed to write {}", target.display()))?;
+                }
+                Change::Delete { path } => {
+                    let target = project_dir.join(path);
+                    fs::remove_file(&target)
+                        .with_context(|| format!("Failed to remove {}", target.display()))?;
+                }
+                Change::Reject { path, patch } => {
+                    let target = project_dir.join(reject_path(path));
+                    if let Some(parent) = target.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(&target, patch)
+                        .with_context(|| format!("Failed to write {}", target.display()))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn print_patch(&self, changes: &[Change], project_dir: &Path) {
+        if changes.is_empty() {
+            output::info("No template changes to apply.");
+            return;
+        }
+
+        output::header("Upgrade patch");
+        for change in changes {
+            match change {
+                Change::Write { path, contents, .. } => {
+                    let ours = fs::read(project_dir.join(path)).ok();
+                    output::plain(&render::unified_diff(path, ours.as_deref(), Some(contents)));
+                }
+                Change::Delete { path } => {
+                    let ours = fs::read(project_dir.join(path)).ok();
+                    output::plain(&render::unified_diff(path, ours.as_deref(), None));
+                }
+                Change::Reject { path, patch } => {
+                    output::warning(&format!(
+                        "Would write {} (change can't be applied automatically):",
+                        reject_path(path).display()
+                    ));
+                    output::plain(patch);
+                }
+            }
+        }
+        self.print_summary(changes);
+    }
+
+    fn print_summary(&self, changes: &[Change]) {
+        let mut conflicted = Vec::new();
+        let mut rejected = Vec::new();
+        let mut applied = 0;
+
+        for change in changes {
+            match change {
+                Change::Write {
+                    path,
+                    conflicted: true,
+                    ..
+                } => conflicted.push(path.display().to_string()),
+                Change::Reject { path, .. } => {
+                    rejected.push(reject_path(path).display().to_string())
+                }
+                _ => applied += 1,
+            }
+        }
+
+        output::success(&format!(
+            "{} file(s) updated cleanly, {} with conflicts, {} rejected",
+            applied,
+            conflicted.len(),
+            rejected.len()
+        ));
+        if !conflicted.is_empty() {
+            output::warning(&format!(
+                "Resolve the conflict markers in: {}",
+                conflicted.join(", ")
+            ));
+        }
+        if !rejected.is_empty() {
+            output::warning(&format!(
+                "Review the rejected changes in: {}",
+                rejected.join(", ")
+            ));
+        }
+        output::result(&serde_json::json!({
+            "applied": applied,
+            "conflicted": conflicted,
+            "rejected": rejected,
+        }));
+    }
+}
+
+/// Three-way merge a file with `git merge-file`
+///
+/// Returns the merged contents and whether they contain conflict markers, or `None`
+/// for binary files which can't be merged line by line.
+fn merge_file(base: &[u8], ours: &[u8], theirs: &[u8]) -> Result<Option<(Vec<u8>, bool)>> {
+    if [base, ours, theirs]
+        .iter()
+        .any(|contents| std::str::from_utf8(contents).is_err())
+    {
+        return Ok(None);
+    }
+
+    let scratch = tempfile::TempDir::new().context("Failed to create temporary directory")?;
+    let [base_file, ours_file, theirs_file] =
+        ["base", "ours", "theirs"].map(|name| scratch.path().join(name));
+    fs::write(&base_file, base)?;
+    fs::write(&ours_file, ours)?;
+    fs::write(&theirs_file, theirs)?;
+
+    let output = Command::new("git")
+        .args([
+            "merge-file",
+            "-p",
+            "-L",
+            "project",
+            "-L",
+            "original",
+            "-L",
+            "template",
+        ])
+        .args([&ours_file, &base_file, &theirs_file])
+        .output()
+        .context("Failed to run git merge-file")?;
+
+    // Exit code is the number of conflicts, negative (> 127) on error
+    match output.status.code() {
+        Some(0) => Ok(Some((output.stdout, false))),
+        Some(code) if (1..=127).contains(&code) => Ok(Some((output.stdout, true))),
+        _ => Err(anyhow!(
+            "git merge-file failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+/// Path of the reject file for `path`
+fn reject_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".rej");
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tree(files: &[(&str, &str)]) -> FileTree {
+        files
+            .iter()
+            .map(|(path, contents)| (PathBuf::from(path), contents.as_bytes().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_changes_untouched_project() {
+        let project = TempDir::new().unwrap();
+        fs::write(project.path().join("README.md"), "old\n").unwrap();
+        fs::write(project.path().join("removed.txt"), "bye\n").unwrap();
+
+        let base = tree(&[("README.md", "old\n"), ("removed.txt", "bye\n")]);
+        let new = tree(&[("README.md", "new\n"), ("added.txt", "hi\n")]);
+
+        let changes = UpgradeCommand
+            .plan_changes(&base, &new, project.path())
+            .unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                Change::Write {
+                    path: PathBuf::from("README.md"),
+                    contents: b"new\n".to_vec(),
+                    conflicted: false,
+                },
+                Change::Write {
+                    path: PathBuf::from("added.txt"),
+                    contents: b"hi\n".to_vec(),
+                    conflicted: false,
+                },
+                Change::Delete {
+                    path: PathBuf::from("removed.txt"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_changes_merges_local_edits() {
+        let project = TempDir::new().unwrap();
+        fs::write(project.path().join("config.toml"), "a = 1\nb = 2\nc = 30\n").unwrap();
+
+        let base = tree(&[("config.toml", "a = 1\nb = 2\nc = 3\n")]);
+        let new = tree(&[("config.toml", "a = 10\nb = 2\nc = 3\n")]);
+
+        let changes = UpgradeCommand
+            .plan_changes(&base, &new, project.path())
+            .unwrap();
+        assert_eq!(
+            changes,
+            vec![Change::Write {
+                path: PathBuf::from("config.toml"),
+                contents: b"a = 10\nb = 2\nc = 30\n".to_vec(),
+                conflicted: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_plan_changes_conflicting_edits() {
+        let project = TempDir::new().unwrap();
+        fs::write(project.path().join("README.md"), "mine\n").unwrap();
+
+        let base = tree(&[("README.md", "original\n")]);
+        let new = tree(&[("README.md", "theirs\n")]);
+
+        let changes = UpgradeCommand
+            .plan_changes(&base, &new, project.path())
+            .unwrap();
+        let Change::Write {
+            contents,
+            conflicted,
+            ..
+        } = &changes[0]
+        else {
+            panic!("expected a write, got {:?}", changes[0]);
+        };
+        assert!(conflicted);
+        let contents = String::from_utf8_lossy(contents);
+        assert!(contents.contains("<<<<<<< project"));
+        assert!(contents.contains(">>>>>>> template"));
+    }
+
+    #[test]
+    fn test_plan_changes_rejects_changes_to_deleted_files() {
+        let project = TempDir::new().unwrap();
+
+        let base = tree(&[("README.md", "original\n")]);
+        let new = tree(&[("README.md", "updated\n")]);
+
+        let changes = UpgradeCommand
+            .plan_changes(&base, &new, project.path())
+            .unwrap();
+        let Change::Reject { path, patch } = &changes[0] else {
+            panic!("expected a reject, got {:?}", changes[0]);
+        };
+        assert_eq!(path, Path::new("README.md"));
+        assert!(patch.contains("+updated"));
+    }
+
+    #[test]
+    fn test_plan_changes_skips_files_already_up_to_date() {
+        let project = TempDir::new().unwrap();
+        fs::write(project.path().join("README.md"), "updated\n").unwrap();
+
+        let base = tree(&[("README.md", "original\n"), ("gone.txt", "x\n")]);
+        let new = tree(&[("README.md", "updated\n")]);
+
+        let changes = UpgradeCommand
+            .plan_changes(&base, &new, project.path())
+            .unwrap();
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_apply_changes_writes_reject_files() {
+        let project = TempDir::new().unwrap();
+        fs::write(project.path().join("old.txt"), "old\n").unwrap();
+
+        let changes = vec![
+            Change::Write {
+                path: PathBuf::from("src/new.txt"),
+                contents: b"new\n".to_vec(),
+                conflicted: false,
+            },
+            Change::Delete {
+                path: PathBuf::from("old.txt"),
+            },
+            Change::Reject {
+                path: PathBuf::from("README.md"),
+                patch: "patch".to_string(),
+            },
+        ];
+        UpgradeCommand
+            .apply_changes(&changes, project.path())
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(project.path().join("src/new.txt")).unwrap(),
+            "new\n"
+        );
+        assert!(!project.path().join("old.txt").exists());
+        assert_eq!(
+            fs::read_to_string(project.path().join("README.md.rej")).unwrap(),
+            "patch"
+        );
+    }
+
+    #[test]
+    fn test_merge_file_binary() {
+        let result = merge_file(&[0xff], &[0xfe], &[0xfd]).unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_upgrade_requires_lock_file() {
+        let project = TempDir::new().unwrap();
+        let args = UpgradeArgs {
+            project_dir: Some(project.path().to_path_buf()),
+            revision: None,
+            dry_run: true,
+        };
+
+        let result = UpgradeCommand.run(&args);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains(LOCK_FILE_NAME));
+    }
+}
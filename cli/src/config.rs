@@ -1,37 +1,61 @@
 //! Configuration management for cza
 //!
 //! This module handles loading, saving, and managing user configuration stored in `~/.config/cza/config.toml`.
+//! [`Config::load`] layers a project `.cza.toml`, `CZA_*` environment variables and `-c`
+//! flags over it (see [`layers`]); commands that change settings edit the global file
+//! only, through [`Config::load_global`].
 //!
 //! ## Configuration Structure
 //!
-//! The configuration is divided into three main sections:
+//! The configuration is divided into four main sections:
 //!
 //! - [`UserConfig`] - User preferences (author, email, default template, git initialization)
-//! - [`DevelopmentConfig`] - Development settings (verbose logging, color output, overwrite confirmation)
+//! - [`DevelopmentConfig`] - Development settings (verbose logging, color output, overwrite confirmation, autocorrect)
 //! - [`PostGenerationConfig`] - Post-generation behavior (auto-install deps, auto-setup hooks, open editor)
+//! - [`RegistryConfig`] - External template registries and their cache lifetime
+//!
+//! Named `[profiles.<name>]` tables hold settings that overlay these sections when the
+//! profile is active, e.g. a work author and email. The top-level `profile` key selects
+//! one by default.
+//!
+//! User-defined templates can additionally be declared as `[templates.<key>]` tables.
+//! They use the same fields as the embedded registry and are merged over it by
+//! [`crate::template::load_registry`]. Default placeholder values per template live in
+//! `[template_values.<key>]` tables.
 //!
 //! ## Example
 //!
 //! ```no_run
 //! use cza::config::Config;
 //!
-//! let mut config = Config::load()?;
+//! let mut config = Config::load_global()?;
 //! config.set("user.author", "John Doe")?;
 //! config.save()?;
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
+use crate::context;
+use crate::error::CzaError;
+use crate::suggest::{NameKind, UnknownName};
+use crate::template::TemplateInfo;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
 mod development;
+pub mod layers;
 mod post_generation;
+mod registry;
+pub mod schema;
 mod user;
 
 pub use development::DevelopmentConfig;
+pub use layers::{ActiveProfile, ConfigOrigin, LayeredConfig, ProfileSet};
 pub use post_generation::PostGenerationConfig;
+pub use registry::RegistryConfig;
+pub use schema::ConfigKey;
 pub use user::UserConfig;
 
 /// Helper function for serde default values
@@ -42,6 +66,10 @@ pub(crate) fn default_true() -> bool {
 /// Main configuration structure for cza
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct Config {
+    /// Profile applied by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+
     /// User preferences
     #[serde(default)]
     pub user: UserConfig,
@@ -53,9 +81,52 @@ pub struct Config {
     /// Post-generation behavior
     #[serde(default)]
     pub post_generation: PostGenerationConfig,
+
+    /// External template registries
+    #[serde(default)]
+    pub registry: RegistryConfig,
+
+    /// User-defined templates, merged over the embedded registry
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub templates: BTreeMap<String, TemplateInfo>,
+
+    /// Default template placeholder values, keyed by template
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub template_values: BTreeMap<String, toml::Table>,
+
+    /// Named sets of settings overlaying the sections above, e.g. `[profiles.work.user]`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, toml::Table>,
 }
 
 impl Config {
+    /// Error for a key that isn't in [`schema::KEYS`], suggesting similar keys
+    pub fn unknown_key(key: &str) -> UnknownName {
+        UnknownName::new(
+            NameKind::ConfigKey,
+            key,
+            schema::KEYS.iter().map(|config_key| config_key.key),
+        )
+    }
+
+    /// Registry entry of `key`, or a [`CzaError::UnknownConfigKey`] error
+    fn schema_key(key: &str) -> Result<&'static ConfigKey> {
+        schema::find(key).ok_or_else(|| CzaError::from(Self::unknown_key(key)).into())
+    }
+
+    /// The configuration as a TOML table
+    fn to_table(&self) -> Result<toml::Table> {
+        toml::Table::try_from(self).context("Failed to serialize config")
+    }
+
+    /// Replace the configuration with the contents of `table`
+    fn update_from(&mut self, table: toml::Table) -> Result<()> {
+        *self = toml::Value::Table(table)
+            .try_into()
+            .context("Failed to apply config change")?;
+        Ok(())
+    }
+
     /// Get the configuration file path
     pub fn config_path() -> Result<PathBuf> {
         let config_dir = dirs::config_dir()
@@ -64,19 +135,61 @@ impl Config {
         Ok(config_dir.join("config.toml"))
     }
 
-    /// Load configuration from disk or create default
+    /// Load the effective configuration: defaults, the global file, the project file,
+    /// the active profile, environment variables and `-c` flags
     pub fn load() -> Result<Self> {
+        Ok(Self::load_layered()?.config)
+    }
+
+    /// Load the effective configuration along with the origin of each key
+    pub fn load_layered() -> Result<LayeredConfig> {
+        Self::sources()?.resolve()
+    }
+
+    /// Profiles defined in the global and project files, and the selected one
+    ///
+    /// Unlike [`Config::load`], this doesn't fail when the selected profile is missing.
+    pub fn load_profiles() -> Result<ProfileSet> {
+        Self::sources()?.profiles()
+    }
+
+    fn sources() -> Result<layers::Sources> {
+        Ok(layers::Sources {
+            global_file: Some(Self::config_path()?),
+            project_file: Self::project_path(),
+            env: std::env::vars()
+                .filter(|(var, _)| var.starts_with(layers::ENV_PREFIX))
+                .collect(),
+            profile: context::profile().map(str::to_string),
+            overrides: context::config_overrides().to_vec(),
+        })
+    }
+
+    /// Load the global configuration file only, or defaults when it doesn't exist
+    pub fn load_global() -> Result<Self> {
         let config_path = Self::config_path()?;
 
         if config_path.exists() {
             let contents =
                 fs::read_to_string(&config_path).context("Failed to read config file")?;
-            toml::from_str(&contents).context("Failed to parse config file")
+            toml::from_str(&contents).map_err(|e| {
+                CzaError::ConfigParse {
+                    path: config_path,
+                    reason: e.to_string().trim_end().to_string(),
+                }
+                .into()
+            })
         } else {
             Ok(Self::default())
         }
     }
 
+    /// The project `.cza.toml` applying to the current directory, if any
+    pub fn project_path() -> Option<PathBuf> {
+        let cwd = std::env::current_dir().ok()?;
+        layers::find_project_file(&cwd)
+    }
+
     /// Save configuration to disk
     pub fn save(&self) -> Result<()> {
         let config_path = Self::config_path()?;
@@ -94,59 +207,30 @@ impl Config {
     }
 
     /// Get a configuration value by key path (e.g., "user.author")
-    pub fn get(&self, key: &str) -> Option<String> {
-        match key {
-            "user.author" => self.user.author.clone(),
-            "user.email" => self.user.email.clone(),
-            "user.git_init" => Some(self.user.git_init.to_string()),
-            "user.default_template" => self.user.default_template.clone(),
-            "development.verbose" => Some(self.development.verbose.to_string()),
-            "development.color" => Some(self.development.color.to_string()),
-            "development.confirm_overwrite" => Some(self.development.confirm_overwrite.to_string()),
-            "post_generation.auto_install_deps" => {
-                Some(self.post_generation.auto_install_deps.to_string())
-            }
-            "post_generation.auto_setup_hooks" => {
-                Some(self.post_generation.auto_setup_hooks.to_string())
-            }
-            "post_generation.open_editor" => self.post_generation.open_editor.clone(),
-            _ => None,
-        }
+    ///
+    /// Returns `Ok(None)` for keys that aren't set and a [`CzaError::UnknownConfigKey`]
+    /// error for keys that don't exist.
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        let (section, name) = Self::schema_key(key)?.path();
+        let table = self.to_table()?;
+        let value = table
+            .get(section)
+            .and_then(|section| section.get(name))
+            .map(|value| match value {
+                toml::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            });
+        Ok(value)
     }
 
-    /// Set a configuration value by key path
+    /// Set a configuration value by key path, checking it against the key's type and
+    /// validator
     pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
-        match key {
-            "user.author" => self.user.author = Some(value.to_string()),
-            "user.email" => self.user.email = Some(value.to_string()),
-            "user.git_init" => {
-                self.user.git_init = value.parse().context("Invalid boolean value")?
-            }
-            "user.default_template" => self.user.default_template = Some(value.to_string()),
-            "development.verbose" => {
-                self.development.verbose = value.parse().context("Invalid boolean value")?
-            }
-            "development.color" => {
-                self.development.color = value.parse().context("Invalid boolean value")?
-            }
-            "development.confirm_overwrite" => {
-                self.development.confirm_overwrite =
-                    value.parse().context("Invalid boolean value")?
-            }
-            "post_generation.auto_install_deps" => {
-                self.post_generation.auto_install_deps =
-                    value.parse().context("Invalid boolean value")?
-            }
-            "post_generation.auto_setup_hooks" => {
-                self.post_generation.auto_setup_hooks =
-                    value.parse().context("Invalid boolean value")?
-            }
-            "post_generation.open_editor" => {
-                self.post_generation.open_editor = Some(value.to_string())
-            }
-            _ => anyhow::bail!("Unknown configuration key: {}", key),
-        }
-        Ok(())
+        let config_key = Self::schema_key(key)?;
+        let parsed = config_key.parse_from(value, ConfigOrigin::CommandLine)?;
+        let mut table = self.to_table()?;
+        layers::set_value(&mut table, config_key, parsed);
+        self.update_from(table)
     }
 
     /// Reset configuration to defaults
@@ -154,59 +238,30 @@ impl Config {
         *self = Self::default();
     }
 
-    /// List all configuration values
+    /// Reset a single key to its default value
+    pub fn reset_key(&mut self, key: &str) -> Result<()> {
+        let (section, name) = Self::schema_key(key)?.path();
+        let mut table = self.to_table()?;
+        if let Some(section) = table.get_mut(section).and_then(|s| s.as_table_mut()) {
+            section.remove(name);
+        }
+        // Missing fields are filled in with their serde defaults
+        self.update_from(table)
+    }
+
+    /// List all configuration values, in [`schema::KEYS`] order
     pub fn list(&self) -> Vec<(String, String)> {
-        vec![
-            (
-                "user.author".to_string(),
-                self.user
-                    .author
-                    .clone()
-                    .unwrap_or_else(|| "<not set>".to_string()),
-            ),
-            (
-                "user.email".to_string(),
-                self.user
-                    .email
-                    .clone()
-                    .unwrap_or_else(|| "<not set>".to_string()),
-            ),
-            ("user.git_init".to_string(), self.user.git_init.to_string()),
-            (
-                "user.default_template".to_string(),
-                self.user
-                    .default_template
-                    .clone()
-                    .unwrap_or_else(|| "<not set>".to_string()),
-            ),
-            (
-                "development.verbose".to_string(),
-                self.development.verbose.to_string(),
-            ),
-            (
-                "development.color".to_string(),
-                self.development.color.to_string(),
-            ),
-            (
-                "development.confirm_overwrite".to_string(),
-                self.development.confirm_overwrite.to_string(),
-            ),
-            (
-                "post_generation.auto_install_deps".to_string(),
-                self.post_generation.auto_install_deps.to_string(),
-            ),
-            (
-                "post_generation.auto_setup_hooks".to_string(),
-                self.post_generation.auto_setup_hooks.to_string(),
-            ),
-            (
-                "post_generation.open_editor".to_string(),
-                self.post_generation
-                    .open_editor
-                    .clone()
-                    .unwrap_or_else(|| "<not set>".to_string()),
-            ),
-        ]
+        schema::KEYS
+            .iter()
+            .map(|config_key| {
+                let value = self
+                    .get(config_key.key)
+                    .ok()
+                    .flatten()
+                    .unwrap_or_else(|| "<not set>".to_string());
+                (config_key.key.to_string(), value)
+            })
+            .collect()
     }
 }
 
@@ -237,13 +292,63 @@ mod tests {
         config.user.author = Some("Test Author".to_string());
         config.user.email = Some("test@example.com".to_string());
 
-        assert_eq!(config.get("user.author"), Some("Test Author".to_string()));
         assert_eq!(
-            config.get("user.email"),
+            config.get("user.author").unwrap(),
+            Some("Test Author".to_string())
+        );
+        assert_eq!(
+            config.get("user.email").unwrap(),
             Some("test@example.com".to_string())
         );
-        assert_eq!(config.get("user.git_init"), Some("true".to_string()));
-        assert_eq!(config.get("invalid.key"), None);
+        assert_eq!(
+            config.get("user.git_init").unwrap(),
+            Some("true".to_string())
+        );
+        assert_eq!(config.get("user.default_template").unwrap(), None);
+        assert!(config.get("invalid.key").is_err());
+    }
+
+    #[test]
+    fn test_unknown_key_suggestions() {
+        let mut config = Config::default();
+        let err = config.set("user.autor", "Someone").unwrap_err();
+        let Some(CzaError::UnknownConfigKey(unknown)) = err.downcast_ref::<CzaError>() else {
+            panic!("unexpected error: {:#}", err);
+        };
+        assert_eq!(unknown.suggestions, vec!["user.author".to_string()]);
+
+        let err = config.get("developement.color").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Did you mean 'development.color'?"));
+    }
+
+    #[test]
+    fn test_schema_defaults_match_config_defaults() {
+        let config = Config::default();
+        for config_key in schema::KEYS {
+            assert_eq!(
+                config.get(config_key.key).unwrap().as_deref(),
+                config_key.default,
+                "{}",
+                config_key.key
+            );
+        }
+    }
+
+    #[test]
+    fn test_reset_key() {
+        let mut config = Config::default();
+        config.set("user.git_init", "false").unwrap();
+        config.set("user.author", "Someone").unwrap();
+        config.set("registry.cache_ttl", "60").unwrap();
+
+        config.reset_key("user.git_init").unwrap();
+        config.reset_key("user.author").unwrap();
+        assert!(config.user.git_init);
+        assert_eq!(config.user.author, None);
+        assert_eq!(config.registry.cache_ttl, 60);
+        assert!(config.reset_key("user.autor").is_err());
     }
 
     #[test]
@@ -259,6 +364,10 @@ mod tests {
         config.set("development.verbose", "true").unwrap();
         assert!(config.development.verbose);
 
+        config.set("registry.cache_ttl", "60").unwrap();
+        assert_eq!(config.registry.cache_ttl, 60);
+        assert!(config.set("registry.cache_ttl", "soon").is_err());
+
         let result = config.set("invalid.key", "value");
         assert!(result.is_err());
     }
@@ -269,7 +378,7 @@ mod tests {
         config.user.author = Some("Test Author".to_string());
 
         let list = config.list();
-        assert_eq!(list.len(), 10);
+        assert_eq!(list.len(), schema::KEYS.len());
 
         let author = list.iter().find(|(k, _)| k == "user.author");
         assert_eq!(
@@ -306,6 +415,76 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_user_templates_roundtrip() {
+        let toml_content = r#"
+[user]
+author = "Test Author"
+
+[templates.my-starter]
+name = "My Starter"
+description = "A custom starter"
+repository = "https://github.com/me/starters"
+subfolder = "my-starter"
+frameworks = ["noir", "vite"]
+revision = "v1.0.0"
+"#;
+        let config: Config = toml::from_str(toml_content).unwrap();
+        let template = &config.templates["my-starter"];
+        assert_eq!(template.name, "My Starter");
+        assert_eq!(template.revision, Some("v1.0.0".to_string()));
+
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        assert!(serialized.contains("[templates.my-starter]"));
+        assert!(!serialized.contains("source"));
+    }
+
+    #[test]
+    fn test_template_values_roundtrip() {
+        let toml_content = r#"
+[template_values.noir-vite]
+backend = "barretenberg"
+use_ci = true
+"#;
+        let config: Config = toml::from_str(toml_content).unwrap();
+        let values = &config.template_values["noir-vite"];
+        assert_eq!(values["backend"].as_str(), Some("barretenberg"));
+        assert_eq!(values["use_ci"].as_bool(), Some(true));
+
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        assert!(serialized.contains("[template_values.noir-vite]"));
+        assert!(!toml::to_string_pretty(&Config::default())
+            .unwrap()
+            .contains("template_values"));
+    }
+
+    #[test]
+    fn test_profiles_roundtrip() {
+        let toml_content = r#"
+profile = "work"
+
+[user]
+author = "Jane Doe"
+
+[profiles.work.user]
+author = "Jane Doe (ACME)"
+email = "jane@acme.example"
+"#;
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert_eq!(config.profile.as_deref(), Some("work"));
+        assert_eq!(
+            config.profiles["work"]["user"]["email"].as_str(),
+            Some("jane@acme.example")
+        );
+        // The base section is untouched until the layers apply the profile
+        assert_eq!(config.user.author.as_deref(), Some("Jane Doe"));
+
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        let reparsed: Config = toml::from_str(&serialized).unwrap();
+        assert_eq!(reparsed.profile.as_deref(), Some("work"));
+        assert_eq!(reparsed.profiles, config.profiles);
+    }
+
     #[test]
     fn test_reset_config() {
         let mut config = Config::default();
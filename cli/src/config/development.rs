@@ -14,6 +14,10 @@ pub struct DevelopmentConfig {
     /// Ask before overwriting existing directories (default: true)
     #[serde(default = "super::default_true")]
     pub confirm_overwrite: bool,
+
+    /// Use the only close match for a mistyped template or config key (default: false)
+    #[serde(default)]
+    pub autocorrect: bool,
 }
 
 impl Default for DevelopmentConfig {
@@ -22,6 +26,7 @@ impl Default for DevelopmentConfig {
             verbose: false,
             color: true,
             confirm_overwrite: true,
+            autocorrect: false,
         }
     }
 }
@@ -0,0 +1,566 @@
+//! Configuration layers
+//!
+//! The effective configuration is built from, in increasing order of precedence:
+//!
+//! 1. built-in defaults
+//! 2. the global file, `~/.config/cza/config.toml`
+//! 3. a project file, `.cza.toml`, found in the current directory or its parents
+//! 4. the active `[profiles.<name>]` table of those files
+//! 5. `CZA_<SECTION>__<KEY>` environment variables, e.g. `CZA_USER__AUTHOR`
+//! 6. `-c/--config <key>=<value>` command line flags
+//!
+//! Files are merged table by table, so a project file only needs the keys it changes and
+//! can also add `[templates.*]` entries. Environment variables and flags accept the keys
+//! of [`schema::KEYS`](super::schema::KEYS).
+//!
+//! The profile is chosen by `--profile`, else `CZA_PROFILE`, else the top-level `profile`
+//! key of the files.
+
+use super::schema::{self, ConfigKey};
+use super::Config;
+use crate::error::CzaError;
+use crate::suggest::{NameKind, UnknownName};
+use anyhow::{anyhow, Context, Result};
+use log::debug;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the project configuration file
+pub const PROJECT_FILE_NAME: &str = ".cza.toml";
+
+/// Prefix of configuration environment variables
+pub const ENV_PREFIX: &str = "CZA_";
+
+/// Environment variable selecting the profile
+pub const PROFILE_ENV_VAR: &str = "CZA_PROFILE";
+
+/// Where an effective configuration value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigOrigin {
+    Default,
+    Global(PathBuf),
+    Project(PathBuf),
+    Env(String),
+    Profile(String),
+    CommandLine,
+}
+
+impl fmt::Display for ConfigOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigOrigin::Default => write!(f, "default"),
+            ConfigOrigin::Global(path) => write!(f, "global config {}", path.display()),
+            ConfigOrigin::Project(path) => write!(f, "project config {}", path.display()),
+            ConfigOrigin::Env(var) => write!(f, "environment variable {}", var),
+            ConfigOrigin::Profile(name) => write!(f, "profile {}", name),
+            ConfigOrigin::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+/// A selected profile and what selected it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveProfile {
+    pub name: String,
+    pub origin: ConfigOrigin,
+}
+
+/// The effective configuration and the origin of each key
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    pub config: Config,
+    /// Origin of every key in [`schema::KEYS`]
+    pub origins: BTreeMap<&'static str, ConfigOrigin>,
+    /// Profile applied over the configuration files
+    pub profile: Option<ActiveProfile>,
+}
+
+/// Profiles defined in the configuration files and the selected one, which may not exist
+#[derive(Debug, Clone, Default)]
+pub struct ProfileSet {
+    pub profiles: BTreeMap<String, toml::Table>,
+    pub active: Option<ActiveProfile>,
+}
+
+/// Inputs of the layering, gathered from the environment by [`Config::load_layered`]
+#[derive(Debug, Default)]
+pub(crate) struct Sources {
+    pub global_file: Option<PathBuf>,
+    pub project_file: Option<PathBuf>,
+    pub env: Vec<(String, String)>,
+    /// Profile given with `--profile`
+    pub profile: Option<String>,
+    pub overrides: Vec<(String, String)>,
+}
+
+/// Defaults merged with the configuration files
+struct FileLayers {
+    table: toml::Table,
+    origins: BTreeMap<&'static str, ConfigOrigin>,
+    profile: Option<ActiveProfile>,
+}
+
+impl Sources {
+    /// Merge every layer into the effective configuration
+    pub(crate) fn resolve(&self) -> Result<LayeredConfig> {
+        let FileLayers {
+            mut table,
+            mut origins,
+            profile,
+        } = self.merge_files()?;
+
+        if let Some(ref active) = profile {
+            debug!("Applying profile {} ({})", active.name, active.origin);
+            let overlay = profile_table(&table, &active.name)?;
+            for config_key in schema::KEYS {
+                if lookup(&overlay, config_key).is_some() {
+                    origins.insert(config_key.key, ConfigOrigin::Profile(active.name.clone()));
+                }
+            }
+            merge_tables(&mut table, overlay);
+        }
+
+        let mut env: Vec<_> = self
+            .env
+            .iter()
+            .filter_map(|(var, value)| {
+                let key = key_from_env_var(var)?;
+                Some((var, key, value))
+            })
+            .collect();
+        env.sort();
+        for (var, key, value) in env {
+            let Some(config_key) = schema::find(&key) else {
+                debug!("Ignoring {}, which doesn't match a config key", var);
+                continue;
+            };
+            let origin = ConfigOrigin::Env(var.clone());
+            let value = config_key.parse_from(value, origin.clone())?;
+            set_value(&mut table, config_key, value);
+            origins.insert(config_key.key, origin);
+        }
+
+        for (key, value) in &self.overrides {
+            let config_key =
+                schema::find(key).ok_or_else(|| CzaError::from(Config::unknown_key(key)))?;
+            let value = config_key.parse_from(value, ConfigOrigin::CommandLine)?;
+            set_value(&mut table, config_key, value);
+            origins.insert(config_key.key, ConfigOrigin::CommandLine);
+        }
+
+        let config = toml::Value::Table(table)
+            .try_into()
+            .context("Failed to apply configuration layers")?;
+        Ok(LayeredConfig {
+            config,
+            origins,
+            profile,
+        })
+    }
+
+    /// Profiles of the configuration files, without applying any layer over them
+    pub(crate) fn profiles(&self) -> Result<ProfileSet> {
+        let FileLayers { table, profile, .. } = self.merge_files()?;
+        let profiles = match table.get("profiles") {
+            Some(profiles) => profiles
+                .clone()
+                .try_into()
+                .context("Invalid [profiles] table, expected [profiles.<name>] sections")?,
+            None => BTreeMap::new(),
+        };
+        Ok(ProfileSet {
+            profiles,
+            active: profile,
+        })
+    }
+
+    /// Merge the files over the defaults and find the selected profile
+    fn merge_files(&self) -> Result<FileLayers> {
+        let mut table = Config::default().to_table()?;
+        let mut origins: BTreeMap<&'static str, ConfigOrigin> = schema::KEYS
+            .iter()
+            .map(|config_key| (config_key.key, ConfigOrigin::Default))
+            .collect();
+        let mut file_profile = None;
+
+        for (path, origin) in [
+            (&self.global_file, ConfigOrigin::Global as fn(PathBuf) -> _),
+            (&self.project_file, ConfigOrigin::Project),
+        ] {
+            let Some(path) = path.as_ref().filter(|path| path.is_file()) else {
+                continue;
+            };
+            debug!("Reading config layer {}", path.display());
+            let layer = read_table(path)?;
+            for config_key in schema::KEYS {
+                if lookup(&layer, config_key).is_some() {
+                    origins.insert(config_key.key, origin(path.clone()));
+                }
+            }
+            if let Some(name) = layer.get("profile").and_then(|name| name.as_str()) {
+                file_profile = Some(ActiveProfile {
+                    name: name.to_string(),
+                    origin: origin(path.clone()),
+                });
+            }
+            merge_tables(&mut table, layer);
+        }
+
+        let flag_profile = self.profile.as_ref().map(|name| ActiveProfile {
+            name: name.clone(),
+            origin: ConfigOrigin::CommandLine,
+        });
+        let env_profile =
+            self.env
+                .iter()
+                .find(|(var, _)| var == PROFILE_ENV_VAR)
+                .map(|(var, name)| ActiveProfile {
+                    name: name.clone(),
+                    origin: ConfigOrigin::Env(var.clone()),
+                });
+        // An empty name, e.g. CZA_PROFILE="", applies no profile
+        let profile = flag_profile
+            .or(env_profile)
+            .or(file_profile)
+            .filter(|active| !active.name.is_empty());
+
+        Ok(FileLayers {
+            table,
+            origins,
+            profile,
+        })
+    }
+}
+
+/// Settings of profile `name`, or a [`CzaError::ProfileNotFound`] error suggesting the
+/// defined ones
+fn profile_table(table: &toml::Table, name: &str) -> Result<toml::Table> {
+    let profiles = table
+        .get("profiles")
+        .and_then(|profiles| profiles.as_table());
+    let mut overlay = profiles
+        .and_then(|profiles| profiles.get(name))
+        .and_then(|profile| profile.as_table())
+        .cloned()
+        .ok_or_else(|| {
+            let names = profiles.into_iter().flat_map(|profiles| profiles.keys());
+            CzaError::from(UnknownName::new(
+                NameKind::Profile,
+                name,
+                names.map(String::as_str),
+            ))
+        })?;
+    // A profile can't select or define profiles
+    overlay.remove("profile");
+    overlay.remove("profiles");
+    Ok(overlay)
+}
+
+/// Settings of a profile as dotted keys and values, e.g. `("user.author", "Jane Doe")`
+pub fn flatten_profile(profile: &toml::Table) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    flatten_into("", profile, &mut entries);
+    entries
+}
+
+fn flatten_into(prefix: &str, table: &toml::Table, entries: &mut Vec<(String, String)>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            toml::Value::Table(table) => flatten_into(&key, table, entries),
+            toml::Value::String(s) => entries.push((key, s.clone())),
+            other => entries.push((key, other.to_string())),
+        }
+    }
+}
+
+/// Find `.cza.toml` in `start` or its closest parent that has one
+pub fn find_project_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// Environment variable overriding `key`, e.g. `CZA_USER__AUTHOR` for `user.author`
+pub fn env_var_name(key: &str) -> String {
+    format!("{}{}", ENV_PREFIX, key.replace('.', "__").to_uppercase())
+}
+
+/// Config key named by a `CZA_*` environment variable
+fn key_from_env_var(var: &str) -> Option<String> {
+    let rest = var.strip_prefix(ENV_PREFIX)?;
+    rest.contains("__")
+        .then(|| rest.replace("__", ".").to_lowercase())
+}
+
+/// Read a TOML configuration file
+fn read_table(path: &Path) -> Result<toml::Table> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+    toml::from_str(&contents).map_err(|e| {
+        CzaError::ConfigParse {
+            path: path.to_path_buf(),
+            reason: e.to_string().trim_end().to_string(),
+        }
+        .into()
+    })
+}
+
+/// Merge `overlay` into `base`, recursing into tables present in both
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn lookup<'a>(table: &'a toml::Table, config_key: &ConfigKey) -> Option<&'a toml::Value> {
+    let (section, name) = config_key.path();
+    table.get(section)?.get(name)
+}
+
+/// Set a key in the TOML form of the configuration, creating its section when missing
+pub(crate) fn set_value(table: &mut toml::Table, config_key: &ConfigKey, value: toml::Value) {
+    let (section, name) = config_key.path();
+    let section = table
+        .entry(section)
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    if !section.is_table() {
+        *section = toml::Value::Table(toml::Table::new());
+    }
+    if let toml::Value::Table(section) = section {
+        section.insert(name.to_string(), value);
+    }
+}
+
+/// Parse a `-c KEY=VALUE` flag
+pub fn parse_override(s: &str) -> Result<(String, String)> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Invalid config override '{}', expected KEY=VALUE", s))?;
+    Ok((key.trim().to_string(), value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sources(dir: &Path) -> Sources {
+        let global = dir.join("config.toml");
+        fs::write(
+            &global,
+            "[user]\nauthor = \"Global Author\"\nemail = \"global@example.com\"\n",
+        )
+        .unwrap();
+        let project = dir.join(PROJECT_FILE_NAME);
+        fs::write(
+            &project,
+            "[user]\nauthor = \"Project Author\"\n\n[post_generation]\nauto_install_deps = false\n",
+        )
+        .unwrap();
+        Sources {
+            global_file: Some(global),
+            project_file: Some(project),
+            env: vec![],
+            profile: None,
+            overrides: vec![],
+        }
+    }
+
+    #[test]
+    fn test_files_layer_over_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let sources = sources(temp_dir.path());
+        let layered = sources.resolve().unwrap();
+
+        assert_eq!(
+            layered.config.user.author.as_deref(),
+            Some("Project Author")
+        );
+        assert_eq!(
+            layered.config.user.email.as_deref(),
+            Some("global@example.com")
+        );
+        assert!(!layered.config.post_generation.auto_install_deps);
+        assert!(layered.config.user.git_init);
+
+        let project_file = sources.project_file.clone().unwrap();
+        assert_eq!(
+            layered.origins["user.author"],
+            ConfigOrigin::Project(project_file)
+        );
+        assert_eq!(
+            layered.origins["user.email"],
+            ConfigOrigin::Global(sources.global_file.clone().unwrap())
+        );
+        assert_eq!(layered.origins["user.git_init"], ConfigOrigin::Default);
+    }
+
+    #[test]
+    fn test_env_and_overrides_win() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut sources = sources(temp_dir.path());
+        sources.env = vec![
+            ("CZA_USER__AUTHOR".to_string(), "Env Author".to_string()),
+            ("CZA_USER__GIT_INIT".to_string(), "false".to_string()),
+            ("CZA_UNRELATED".to_string(), "ignored".to_string()),
+            ("CZA_NOT__A_KEY".to_string(), "ignored".to_string()),
+        ];
+        sources.overrides = vec![("user.git_init".to_string(), "true".to_string())];
+        let layered = sources.resolve().unwrap();
+
+        assert_eq!(layered.config.user.author.as_deref(), Some("Env Author"));
+        assert!(layered.config.user.git_init);
+        assert_eq!(
+            layered.origins["user.author"],
+            ConfigOrigin::Env("CZA_USER__AUTHOR".to_string())
+        );
+        assert_eq!(layered.origins["user.git_init"], ConfigOrigin::CommandLine);
+    }
+
+    #[test]
+    fn test_profiles() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut sources = sources(temp_dir.path());
+        fs::write(
+            sources.global_file.as_ref().unwrap(),
+            r#"
+profile = "personal"
+
+[user]
+author = "Global Author"
+
+[profiles.personal.user]
+email = "me@example.com"
+
+[profiles.work.user]
+author = "Work Author"
+email = "me@acme.example"
+
+[profiles.work.post_generation]
+auto_install_deps = true
+"#,
+        )
+        .unwrap();
+
+        // The file selects `personal`, which only sets the email
+        let layered = sources.resolve().unwrap();
+        assert_eq!(layered.profile.as_ref().unwrap().name, "personal");
+        assert_eq!(layered.config.user.email.as_deref(), Some("me@example.com"));
+        assert_eq!(
+            layered.config.user.author.as_deref(),
+            Some("Project Author")
+        );
+
+        // CZA_PROFILE beats the file and --profile beats CZA_PROFILE
+        sources.env = vec![(PROFILE_ENV_VAR.to_string(), "work".to_string())];
+        let layered = sources.resolve().unwrap();
+        assert_eq!(layered.config.user.author.as_deref(), Some("Work Author"));
+        // The profile overrides the project file, environment variables override it
+        assert!(layered.config.post_generation.auto_install_deps);
+        assert_eq!(
+            layered.origins["user.author"],
+            ConfigOrigin::Profile("work".to_string())
+        );
+        assert_eq!(
+            layered.profile.unwrap().origin,
+            ConfigOrigin::Env(PROFILE_ENV_VAR.to_string())
+        );
+
+        sources.profile = Some("wrok".to_string());
+        let err = sources.resolve().unwrap_err();
+        let Some(CzaError::ProfileNotFound(unknown)) = err.downcast_ref::<CzaError>() else {
+            panic!("unexpected error: {:#}", err);
+        };
+        assert_eq!(unknown.suggestions, vec!["work".to_string()]);
+
+        // Listing profiles works even when the selected one is missing
+        let profile_set = sources.profiles().unwrap();
+        assert_eq!(
+            profile_set.profiles.keys().collect::<Vec<_>>(),
+            vec!["personal", "work"]
+        );
+        assert_eq!(profile_set.active.unwrap().name, "wrok");
+
+        sources.profile = Some(String::new());
+        assert!(sources.resolve().unwrap().profile.is_none());
+    }
+
+    #[test]
+    fn test_flatten_profile() {
+        let profile: toml::Table =
+            toml::from_str("[user]\nauthor = \"Jane\"\ngit_init = false\n").unwrap();
+        assert_eq!(
+            flatten_profile(&profile),
+            vec![
+                ("user.author".to_string(), "Jane".to_string()),
+                ("user.git_init".to_string(), "false".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_layers() {
+        let env = Sources {
+            env: vec![("CZA_USER__GIT_INIT".to_string(), "maybe".to_string())],
+            ..Default::default()
+        };
+        let err = env.resolve().unwrap_err();
+        assert!(err.to_string().contains("CZA_USER__GIT_INIT"));
+        assert_eq!(crate::error::exit_code(&err), 20);
+
+        let unknown = Sources {
+            overrides: vec![("user.autor".to_string(), "x".to_string())],
+            ..Default::default()
+        };
+        assert!(unknown.resolve().is_err());
+    }
+
+    #[test]
+    fn test_find_project_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_project_file(&nested), None);
+
+        fs::write(temp_dir.path().join(PROJECT_FILE_NAME), "").unwrap();
+        assert_eq!(
+            find_project_file(&nested),
+            Some(temp_dir.path().join(PROJECT_FILE_NAME))
+        );
+    }
+
+    #[test]
+    fn test_env_var_names() {
+        assert_eq!(env_var_name("user.author"), "CZA_USER__AUTHOR");
+        assert_eq!(
+            key_from_env_var("CZA_POST_GENERATION__AUTO_INSTALL_DEPS").as_deref(),
+            Some("post_generation.auto_install_deps")
+        );
+        assert_eq!(key_from_env_var("CZA_LOG"), None);
+        assert_eq!(key_from_env_var("HOME"), None);
+    }
+
+    #[test]
+    fn test_parse_override() {
+        assert_eq!(
+            parse_override("user.author=Jane=Doe").unwrap(),
+            ("user.author".to_string(), "Jane=Doe".to_string())
+        );
+        assert!(parse_override("user.author").is_err());
+    }
+}
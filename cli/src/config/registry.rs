@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Default time-to-live for cached remote registries (one day)
+pub(crate) fn default_cache_ttl() -> u64 {
+    24 * 60 * 60
+}
+
+/// External template registries configuration
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RegistryConfig {
+    /// How long a fetched remote registry is reused before refetching, in seconds (default: 86400)
+    #[serde(default = "default_cache_ttl")]
+    pub cache_ttl: u64,
+
+    /// Extra registries keyed by namespace, pointing at a URL or a file path
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sources: BTreeMap<String, String>,
+}
+
+impl Default for RegistryConfig {
+    fn default() -> Self {
+        Self {
+            cache_ttl: default_cache_ttl(),
+            sources: BTreeMap::new(),
+        }
+    }
+}
@@ -0,0 +1,286 @@
+//! Declarative registry of the configuration keys
+//!
+//! Every scalar setting is described once here, with its type, default, description and
+//! an optional validator. [`Config::get`](super::Config::get), `set`, `list` and
+//! `reset_key` read and write keys through the TOML form of the configuration, so adding
+//! a field to a section only takes a new [`ConfigKey`] entry. `cza config schema` turns
+//! the registry into a JSON Schema.
+
+use super::ConfigOrigin;
+use crate::error::CzaError;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Map, Value};
+
+/// Type of a configuration value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Boolean,
+    String,
+    Integer,
+}
+
+impl ValueType {
+    /// JSON Schema type name
+    pub fn json_type(self) -> &'static str {
+        match self {
+            ValueType::Boolean => "boolean",
+            ValueType::String => "string",
+            ValueType::Integer => "integer",
+        }
+    }
+}
+
+/// A configuration key, addressed as `section.name`
+#[derive(Debug)]
+pub struct ConfigKey {
+    pub key: &'static str,
+    pub value_type: ValueType,
+    /// Default value, `None` when the key is unset by default
+    pub default: Option<&'static str>,
+    pub description: &'static str,
+    /// Extra check on top of the type, returning why a value is rejected
+    pub validate: Option<fn(&str) -> Result<()>>,
+}
+
+impl ConfigKey {
+    /// Section and field name
+    pub fn path(&self) -> (&'static str, &'static str) {
+        self.key.split_once('.').unwrap_or(("", self.key))
+    }
+
+    /// Parse and validate a value given on the command line
+    pub fn parse(&self, value: &str) -> Result<toml::Value> {
+        let parsed = match self.value_type {
+            ValueType::Boolean => toml::Value::Boolean(value.parse().map_err(|_| {
+                anyhow!("Invalid boolean value '{}', expected true or false", value)
+            })?),
+            ValueType::Integer => toml::Value::Integer(
+                value
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|number| *number >= 0)
+                    .ok_or_else(|| {
+                        anyhow!("Invalid number '{}', expected a whole number", value)
+                    })?,
+            ),
+            ValueType::String => toml::Value::String(value.to_string()),
+        };
+        if let Some(validate) = self.validate {
+            validate(value)?;
+        }
+        Ok(parsed)
+    }
+
+    /// [`ConfigKey::parse`] a value coming from `origin`, failing with
+    /// [`CzaError::InvalidConfigValue`]
+    pub fn parse_from(&self, value: &str, origin: ConfigOrigin) -> Result<toml::Value, CzaError> {
+        self.parse(value).map_err(|e| CzaError::InvalidConfigValue {
+            key: self.key.to_string(),
+            origin,
+            reason: format!("{:#}", e),
+        })
+    }
+
+    /// JSON Schema of the value
+    fn json_schema(&self) -> Value {
+        let mut schema = Map::new();
+        schema.insert("type".into(), json!(self.value_type.json_type()));
+        schema.insert("description".into(), json!(self.description));
+        if let Some(default) = self.default {
+            let default = match self.value_type {
+                ValueType::Boolean => json!(default == "true"),
+                ValueType::Integer => json!(default.parse::<u64>().unwrap_or_default()),
+                ValueType::String => json!(default),
+            };
+            schema.insert("default".into(), default);
+        }
+        if self.value_type == ValueType::Integer {
+            schema.insert("minimum".into(), json!(0));
+        }
+        Value::Object(schema)
+    }
+}
+
+/// All configuration keys, in display order
+pub const KEYS: &[ConfigKey] = &[
+    ConfigKey {
+        key: "user.author",
+        value_type: ValueType::String,
+        default: None,
+        description: "Default author name for new projects",
+        validate: Some(not_empty),
+    },
+    ConfigKey {
+        key: "user.email",
+        value_type: ValueType::String,
+        default: None,
+        description: "Default email for project metadata",
+        validate: Some(email),
+    },
+    ConfigKey {
+        key: "user.git_init",
+        value_type: ValueType::Boolean,
+        default: Some("true"),
+        description: "Initialize a git repository in new projects",
+        validate: None,
+    },
+    ConfigKey {
+        key: "user.default_template",
+        value_type: ValueType::String,
+        default: None,
+        description: "Template used by 'cza new' when --template is not given",
+        validate: Some(not_empty),
+    },
+    ConfigKey {
+        key: "development.verbose",
+        value_type: ValueType::Boolean,
+        default: Some("false"),
+        description: "Enable debug-level logging output",
+        validate: None,
+    },
+    ConfigKey {
+        key: "development.color",
+        value_type: ValueType::Boolean,
+        default: Some("true"),
+        description: "Use colored output in the terminal",
+        validate: None,
+    },
+    ConfigKey {
+        key: "development.confirm_overwrite",
+        value_type: ValueType::Boolean,
+        default: Some("true"),
+        description: "Refuse to generate into an existing directory",
+        validate: None,
+    },
+    ConfigKey {
+        key: "development.autocorrect",
+        value_type: ValueType::Boolean,
+        default: Some("false"),
+        description: "Use the only close match for a mistyped template or config key",
+        validate: None,
+    },
+    ConfigKey {
+        key: "post_generation.auto_install_deps",
+        value_type: ValueType::Boolean,
+        default: Some("true"),
+        description: "Run 'mise install' after generating a project",
+        validate: None,
+    },
+    ConfigKey {
+        key: "post_generation.auto_setup_hooks",
+        value_type: ValueType::Boolean,
+        default: Some("true"),
+        description: "Run 'hk install' after generating a project",
+        validate: None,
+    },
+    ConfigKey {
+        key: "post_generation.open_editor",
+        value_type: ValueType::String,
+        default: None,
+        description: "Editor command to open new projects with",
+        validate: Some(not_empty),
+    },
+    ConfigKey {
+        key: "registry.cache_ttl",
+        value_type: ValueType::Integer,
+        default: Some("86400"),
+        description: "Seconds a fetched remote registry is reused before refetching",
+        validate: None,
+    },
+];
+
+/// Look up a key in the registry
+pub fn find(key: &str) -> Option<&'static ConfigKey> {
+    KEYS.iter().find(|config_key| config_key.key == key)
+}
+
+/// JSON Schema (draft 2020-12) of the configuration file
+pub fn json_schema() -> Value {
+    let mut sections: Map<String, Value> = Map::new();
+    for config_key in KEYS {
+        let (section, name) = config_key.path();
+        let section = sections.entry(section).or_insert_with(|| {
+            json!({
+                "type": "object",
+                "properties": {},
+            })
+        });
+        section["properties"][name] = config_key.json_schema();
+    }
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "cza configuration",
+        "description": "Settings in ~/.config/cza/config.toml",
+        "type": "object",
+        "properties": sections,
+    })
+}
+
+fn not_empty(value: &str) -> Result<()> {
+    if value.trim().is_empty() {
+        return Err(anyhow!("Value cannot be empty"));
+    }
+    Ok(())
+}
+
+fn email(value: &str) -> Result<()> {
+    match value.split_once('@') {
+        Some((local, domain)) if !local.is_empty() && !domain.is_empty() => Ok(()),
+        _ => Err(anyhow!("Invalid email address '{}'", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_are_unique_and_sectioned() {
+        for (index, config_key) in KEYS.iter().enumerate() {
+            assert!(config_key.key.contains('.'), "{}", config_key.key);
+            assert!(
+                KEYS[..index]
+                    .iter()
+                    .all(|other| other.key != config_key.key),
+                "duplicate key {}",
+                config_key.key
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let git_init = find("user.git_init").unwrap();
+        assert_eq!(
+            git_init.parse("false").unwrap(),
+            toml::Value::Boolean(false)
+        );
+        assert!(git_init.parse("nope").is_err());
+
+        let ttl = find("registry.cache_ttl").unwrap();
+        assert_eq!(ttl.parse("60").unwrap(), toml::Value::Integer(60));
+        assert!(ttl.parse("-1").is_err());
+        assert!(ttl.parse("soon").is_err());
+
+        let email = find("user.email").unwrap();
+        assert!(email.parse("dev@example.com").is_ok());
+        assert!(email.parse("dev").is_err());
+        assert!(find("user.author").unwrap().parse("  ").is_err());
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let schema = json_schema();
+        let git_init = &schema["properties"]["user"]["properties"]["git_init"];
+        assert_eq!(git_init["type"], "boolean");
+        assert_eq!(git_init["default"], true);
+        assert_eq!(
+            schema["properties"]["registry"]["properties"]["cache_ttl"]["default"],
+            86400
+        );
+        assert!(schema["properties"]["user"]["properties"]["author"]
+            .get("default")
+            .is_none());
+    }
+}
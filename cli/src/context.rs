@@ -0,0 +1,124 @@
+//! Global CLI flags and the configuration
+//!
+//! Flags such as `--offline`, `--output`, `--profile`, `-c`, `-v`, `-q` and `--color` apply
+//! to every command. They are parsed once into [`GlobalArgs`] by `main` and stored for the
+//! lifetime of the process, so code deep in the call stack (registry loading, template
+//! caching) can query them without threading them through every command.
+//!
+//! `main` then resolves the configuration once with [`init_config`], and commands and
+//! [`output`](crate::output) share it through [`config`].
+
+use crate::config::Config;
+use crate::output::{ColorChoice, OutputFormat};
+use anyhow::Result;
+use clap::{ArgAction, Args};
+use std::sync::OnceLock;
+
+/// Flags accepted by every command
+#[derive(Args, Debug, Clone, Default)]
+pub struct GlobalArgs {
+    /// Never access the network: use cached templates and registries only
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Output format: styled text, or newline-delimited JSON events for scripts
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t,
+        value_name = "FORMAT"
+    )]
+    pub output: OutputFormat,
+
+    /// Configuration profile to apply (overrides CZA_PROFILE and the `profile` setting)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Override a configuration value for this run (repeatable), e.g. -c user.git_init=false
+    #[arg(
+        short = 'c',
+        long = "config",
+        global = true,
+        value_name = "KEY=VALUE",
+        value_parser = crate::config::layers::parse_override
+    )]
+    pub config_overrides: Vec<(String, String)>,
+
+    /// Print debug logs (-v) or trace logs (-vv), overriding development.verbose
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Only print warnings, errors and command output
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// When to use colors, overriding development.color. `auto` honors NO_COLOR and
+    /// CLICOLOR_FORCE and colors terminals only
+    #[arg(long, global = true, value_enum, value_name = "WHEN")]
+    pub color: Option<ColorChoice>,
+}
+
+static GLOBAL_ARGS: OnceLock<GlobalArgs> = OnceLock::new();
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Store the global flags for this process. Only the first call has an effect.
+pub fn init(args: GlobalArgs) {
+    let _ = GLOBAL_ARGS.set(args);
+}
+
+/// Global flags, or their defaults when [`init`] hasn't been called (e.g. in tests)
+fn global_args() -> &'static GlobalArgs {
+    GLOBAL_ARGS.get_or_init(GlobalArgs::default)
+}
+
+/// Whether `--offline` was given
+pub fn offline() -> bool {
+    global_args().offline
+}
+
+/// Format selected with `--output`
+pub fn output_format() -> OutputFormat {
+    global_args().output
+}
+
+/// Configuration values given with `-c`, in command line order
+pub fn config_overrides() -> &'static [(String, String)] {
+    &global_args().config_overrides
+}
+
+/// Profile given with `--profile`
+pub fn profile() -> Option<&'static str> {
+    global_args().profile.as_deref()
+}
+
+/// Number of `-v` flags
+pub fn verbose() -> u8 {
+    global_args().verbose
+}
+
+/// Whether `-q` was given
+pub fn quiet() -> bool {
+    global_args().quiet
+}
+
+/// Color mode given with `--color`
+pub fn color() -> Option<ColorChoice> {
+    global_args().color
+}
+
+/// Share the configuration resolved for this process. Only the first call has an effect.
+pub fn init_config(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+/// The configuration resolved by [`init_config`], loaded again when it wasn't resolved,
+/// e.g. in tests or when it failed to load at startup, so the error is reported by the
+/// command needing it
+pub fn config() -> Result<Config> {
+    match CONFIG.get() {
+        Some(config) => Ok(config.clone()),
+        None => Config::load(),
+    }
+}
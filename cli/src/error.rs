@@ -0,0 +1,383 @@
+//! Typed errors with stable codes
+//!
+//! Failures users can act on are reported as a [`CzaError`]. Each variant has a stable
+//! [`code`](CzaError::code) for scripts, a hint and its own process exit code, so
+//! [`crate::output::format_error`] never has to guess from the message. Other failures
+//! stay plain [`anyhow::Error`]s and exit with [`GENERIC_EXIT_CODE`].
+//!
+//! Commands keep returning [`anyhow::Result`]; a `CzaError` can be wrapped in context and
+//! is still found by [`find`]. [`ErrorReport`] describes any error for humans or as JSON.
+
+use crate::config::ConfigOrigin;
+use crate::suggest::{self, NameKind, UnknownName};
+use crate::template::compat::Incompatibility;
+use serde::Serialize;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Exit code of errors that aren't a [`CzaError`]
+pub const GENERIC_EXIT_CODE: i32 = 1;
+
+/// Code of errors that aren't a [`CzaError`]
+pub const GENERIC_CODE: &str = "error";
+
+/// Exit code of command line usage errors, as used by clap
+pub const USAGE_EXIT_CODE: i32 = 2;
+
+#[derive(Debug, Error)]
+pub enum CzaError {
+    #[error("{0}")]
+    TemplateNotFound(UnknownName),
+
+    #[error("{0}")]
+    UnknownConfigKey(UnknownName),
+
+    #[error("{0}")]
+    ProfileNotFound(UnknownName),
+
+    #[error("The following required argument was not provided: {argument}")]
+    MissingArgument { argument: String, usage: String },
+
+    #[error("Project name {reason}")]
+    InvalidProjectName { name: String, reason: &'static str },
+
+    #[error("Directory '{}' already exists", path.display())]
+    DestinationExists { path: PathBuf },
+
+    #[error("Template '{template}' ({repository}) at revision {revision} is not cached and --offline was given")]
+    TemplateNotCached {
+        template: String,
+        repository: String,
+        revision: String,
+    },
+
+    #[error("Failed to clone {repository}: {reason}")]
+    CloneFailed { repository: String, reason: String },
+
+    #[error("Failed to generate project: {reason}")]
+    GenerationFailed { reason: String },
+
+    #[error("{command} failed: {reason}")]
+    PostGenFailed { command: String, reason: String },
+
+    #[error("Failed to parse config file {}: {reason}", path.display())]
+    ConfigParse { path: PathBuf, reason: String },
+
+    #[error("Invalid value for {key} from {origin}: {reason}")]
+    InvalidConfigValue {
+        key: String,
+        origin: ConfigOrigin,
+        reason: String,
+    },
+
+    #[error("Template '{template}' {incompatibility}, but this is cza {version}")]
+    IncompatibleTemplate {
+        template: String,
+        incompatibility: Incompatibility,
+        version: String,
+    },
+
+    #[error(
+        "Template '{template}' requires tools that are missing or outdated:\n{}",
+        bullet_list(unmet)
+    )]
+    MissingPrerequisites {
+        template: String,
+        unmet: Vec<String>,
+    },
+}
+
+impl CzaError {
+    /// Stable identifier, e.g. `template_not_found`
+    pub fn code(&self) -> &'static str {
+        match self {
+            CzaError::TemplateNotFound(_) => "template_not_found",
+            CzaError::UnknownConfigKey(_) => "unknown_config_key",
+            CzaError::ProfileNotFound(_) => "profile_not_found",
+            CzaError::MissingArgument { .. } => "missing_argument",
+            CzaError::InvalidProjectName { .. } => "invalid_project_name",
+            CzaError::DestinationExists { .. } => "destination_exists",
+            CzaError::TemplateNotCached { .. } => "template_not_cached",
+            CzaError::CloneFailed { .. } => "clone_failed",
+            CzaError::GenerationFailed { .. } => "generation_failed",
+            CzaError::PostGenFailed { .. } => "post_generation_failed",
+            CzaError::ConfigParse { .. } => "config_parse_error",
+            CzaError::InvalidConfigValue { .. } => "invalid_config_value",
+            CzaError::IncompatibleTemplate { .. } => "incompatible_template",
+            CzaError::MissingPrerequisites { .. } => "missing_prerequisites",
+        }
+    }
+
+    /// Process exit code, distinct for every variant
+    ///
+    /// 1 is left for other errors and 2 for command line usage errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CzaError::MissingArgument { .. } => USAGE_EXIT_CODE,
+            CzaError::TemplateNotFound(_) => 10,
+            CzaError::UnknownConfigKey(_) => 11,
+            CzaError::ProfileNotFound(_) => 12,
+            CzaError::InvalidProjectName { .. } => 13,
+            CzaError::DestinationExists { .. } => 14,
+            CzaError::TemplateNotCached { .. } => 15,
+            CzaError::CloneFailed { .. } => 16,
+            CzaError::GenerationFailed { .. } => 17,
+            CzaError::PostGenFailed { .. } => 18,
+            CzaError::ConfigParse { .. } => 19,
+            CzaError::InvalidConfigValue { .. } => 20,
+            CzaError::IncompatibleTemplate { .. } => 21,
+            CzaError::MissingPrerequisites { .. } => 22,
+        }
+    }
+
+    /// What the user can do about it
+    pub fn hint(&self) -> String {
+        match self {
+            CzaError::TemplateNotFound(unknown)
+            | CzaError::UnknownConfigKey(unknown)
+            | CzaError::ProfileNotFound(unknown) => unknown.hint().to_string(),
+            CzaError::MissingArgument { usage, .. } => {
+                format!("{}\n\nFor more information, try '--help'.", usage)
+            }
+            CzaError::InvalidProjectName { .. } => {
+                "Project names must start with a letter and can only contain alphanumeric characters, hyphens, and underscores.".to_string()
+            }
+            CzaError::DestinationExists { .. } => {
+                "Choose a different project name or remove the existing directory.".to_string()
+            }
+            CzaError::TemplateNotCached { .. } => {
+                "Run 'cza cache warm' while online to cache it.".to_string()
+            }
+            CzaError::CloneFailed { .. } => {
+                "Check the repository URL and your network connection, or use --offline with a cached template.".to_string()
+            }
+            CzaError::GenerationFailed { .. } => {
+                "Run 'cza info <template>' to see the values the template expects.".to_string()
+            }
+            CzaError::PostGenFailed { command, .. } => {
+                format!("You can run '{}' manually in the project directory.", command)
+            }
+            CzaError::ConfigParse { .. } => {
+                "Fix the file, or move it aside to start from defaults.".to_string()
+            }
+            CzaError::InvalidConfigValue { .. } => {
+                "Run 'cza config schema' to see the expected types.".to_string()
+            }
+            CzaError::IncompatibleTemplate {
+                incompatibility, ..
+            } => format!("{}.", incompatibility.hint()),
+            CzaError::MissingPrerequisites { .. } => {
+                "Install them (see 'cza doctor') or pass --skip-prereq-check to generate anyway.".to_string()
+            }
+        }
+    }
+
+    /// The unknown name of not-found errors, with its suggestions
+    pub fn unknown_name(&self) -> Option<&UnknownName> {
+        match self {
+            CzaError::TemplateNotFound(unknown)
+            | CzaError::UnknownConfigKey(unknown)
+            | CzaError::ProfileNotFound(unknown) => Some(unknown),
+            _ => None,
+        }
+    }
+}
+
+impl From<UnknownName> for CzaError {
+    fn from(unknown: UnknownName) -> Self {
+        match unknown.kind {
+            NameKind::Template => CzaError::TemplateNotFound(unknown),
+            NameKind::ConfigKey => CzaError::UnknownConfigKey(unknown),
+            NameKind::Profile => CzaError::ProfileNotFound(unknown),
+        }
+    }
+}
+
+fn bullet_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|item| format!("  - {}", item))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The [`CzaError`] behind `err`, looking through any added context
+pub fn find(err: &anyhow::Error) -> Option<&CzaError> {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<CzaError>())
+}
+
+/// Process exit code for `err`
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    find(err).map_or(GENERIC_EXIT_CODE, CzaError::exit_code)
+}
+
+/// An error as shown to the user, or printed as JSON by commands given `--json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorReport {
+    pub code: &'static str,
+    pub exit_code: i32,
+    /// The message, including the context added by callers
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    /// Close matches for a mistyped template, config key or profile
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+}
+
+impl ErrorReport {
+    pub fn new(err: &anyhow::Error) -> Self {
+        let Some(cza_error) = find(err) else {
+            return Self {
+                code: GENERIC_CODE,
+                exit_code: GENERIC_EXIT_CODE,
+                message: format!("{:#}", err),
+                hint: None,
+                suggestions: Vec::new(),
+            };
+        };
+
+        // Suggestions get their own line instead of the "Did you mean" sentence
+        let (message, suggestions) = match cza_error.unknown_name() {
+            Some(unknown) => (unknown.message(), unknown.suggestions.clone()),
+            None => (format!("{:#}", err), Vec::new()),
+        };
+        Self {
+            code: cza_error.code(),
+            exit_code: cza_error.exit_code(),
+            message,
+            hint: Some(cza_error.hint()),
+            suggestions,
+        }
+    }
+
+    /// "Did you mean" line, if there is anything to suggest
+    pub fn did_you_mean(&self) -> Option<String> {
+        suggest::did_you_mean(&self.suggestions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_codes_and_exit_codes_are_distinct() {
+        let errors = [
+            CzaError::from(UnknownName::new(NameKind::Template, "x", [])),
+            CzaError::from(UnknownName::new(NameKind::ConfigKey, "x", [])),
+            CzaError::from(UnknownName::new(NameKind::Profile, "x", [])),
+            CzaError::MissingArgument {
+                argument: "<X>".to_string(),
+                usage: "Usage: cza x <X>".to_string(),
+            },
+            CzaError::InvalidProjectName {
+                name: "1x".to_string(),
+                reason: "must start with a letter",
+            },
+            CzaError::DestinationExists {
+                path: PathBuf::from("x"),
+            },
+            CzaError::TemplateNotCached {
+                template: "x".to_string(),
+                repository: "x".to_string(),
+                revision: "latest".to_string(),
+            },
+            CzaError::CloneFailed {
+                repository: "x".to_string(),
+                reason: "x".to_string(),
+            },
+            CzaError::GenerationFailed {
+                reason: "x".to_string(),
+            },
+            CzaError::PostGenFailed {
+                command: "mise install".to_string(),
+                reason: "x".to_string(),
+            },
+            CzaError::ConfigParse {
+                path: PathBuf::from("x"),
+                reason: "x".to_string(),
+            },
+            CzaError::InvalidConfigValue {
+                key: "x".to_string(),
+                origin: ConfigOrigin::CommandLine,
+                reason: "x".to_string(),
+            },
+            CzaError::IncompatibleTemplate {
+                template: "x".to_string(),
+                incompatibility: Incompatibility::TooOld {
+                    min: "9.0.0".to_string(),
+                },
+                version: "1.0.0".to_string(),
+            },
+            CzaError::MissingPrerequisites {
+                template: "x".to_string(),
+                unmet: vec!["x".to_string()],
+            },
+        ];
+        for (index, error) in errors.iter().enumerate() {
+            assert_ne!(error.exit_code(), GENERIC_EXIT_CODE);
+            assert!(error.hint().ends_with('.'));
+            for other in &errors[..index] {
+                assert_ne!(error.code(), other.code());
+                assert_ne!(error.exit_code(), other.exit_code());
+            }
+        }
+    }
+
+    #[test]
+    fn test_report_through_context() {
+        let err = anyhow::Error::from(CzaError::DestinationExists {
+            path: PathBuf::from("my-app"),
+        })
+        .context("Could not create project");
+        assert_eq!(exit_code(&err), 14);
+
+        let report = ErrorReport::new(&err);
+        assert_eq!(report.code, "destination_exists");
+        assert_eq!(
+            report.message,
+            "Could not create project: Directory 'my-app' already exists"
+        );
+        assert!(report.hint.unwrap().contains("different project name"));
+    }
+
+    #[test]
+    fn test_report_unknown_name() {
+        let err = anyhow::Error::from(CzaError::from(UnknownName::new(
+            NameKind::Template,
+            "noir-vit",
+            ["noir-vite", "cairo-vite"],
+        )));
+        let report = ErrorReport::new(&err);
+        assert_eq!(report.message, "Template 'noir-vit' not found");
+        assert_eq!(report.suggestions, vec!["noir-vite".to_string()]);
+        assert_eq!(
+            report.did_you_mean().as_deref(),
+            Some("Did you mean 'noir-vite'?")
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["code"], "template_not_found");
+        assert_eq!(json["exit_code"], 10);
+        assert_eq!(json["suggestions"][0], "noir-vite");
+    }
+
+    #[test]
+    fn test_report_generic() {
+        let err = Err::<(), _>(anyhow::anyhow!("disk full"))
+            .context("Failed to write lock file")
+            .unwrap_err();
+        assert_eq!(exit_code(&err), GENERIC_EXIT_CODE);
+        let report = ErrorReport::new(&err);
+        assert_eq!(report.code, GENERIC_CODE);
+        assert_eq!(report.message, "Failed to write lock file: disk full");
+        assert_eq!(report.hint, None);
+        assert!(serde_json::to_value(&report)
+            .unwrap()
+            .get("suggestions")
+            .is_none());
+    }
+}
@@ -76,19 +76,35 @@
 //!
 //! ## Modules
 //!
-//! - [`cmd`] - Command implementations (new, list, config, update)
+//! - [`cache`] - Local cache of template repositories
+//! - [`cmd`] - Command implementations (new, list, search, info, config, update, upgrade, diff, cache, doctor)
 //! - [`config`] - Configuration management
+//! - [`context`] - Global CLI flags
+//! - [`lock`] - Project provenance lock file (`.cza.lock`)
 //! - [`output`] - Formatted terminal output
+//! - [`render`] - Template rendering into scratch directories
+//! - [`suggest`] - "Did you mean" suggestions for mistyped names
 //! - [`template`] - Template registry and validation
 //! - [`utils`] - Utility functions
 
+pub mod cache;
 pub mod cmd;
 pub mod config;
+pub mod context;
+pub mod error;
+pub mod lock;
 pub mod output;
+pub mod render;
+pub mod run_log;
+pub mod suggest;
 pub mod template;
 pub mod utils;
 
-use crate::cmd::{config::ConfigArgs, list::ListArgs, new::NewArgs, update::UpdateArgs};
+use crate::cmd::{
+    cache::CacheArgs, config::ConfigArgs, diff::DiffArgs, doctor::DoctorArgs, info::InfoArgs,
+    list::ListArgs, new::NewArgs, search::SearchArgs, update::UpdateArgs, upgrade::UpgradeArgs,
+};
+use crate::context::GlobalArgs;
 use clap::{Parser, Subcommand};
 
 /// CLI tool to create zero-knowledge applications
@@ -100,6 +116,9 @@ use clap::{Parser, Subcommand};
     about = "CLI tool for scaffolding zero-knowledge application projects"
 )]
 pub struct Cli {
+    #[command(flatten)]
+    pub global: GlobalArgs,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -111,8 +130,20 @@ pub enum Command {
     New(NewArgs),
     /// List available templates and frameworks
     List(ListArgs),
+    /// Search templates by key, name, description and frameworks
+    Search(SearchArgs),
+    /// Show a template's README, placeholders, setup steps and latest commit
+    Info(InfoArgs),
     /// Configure global settings for the CLI
     Config(ConfigArgs),
     /// Update the CLI tool to the latest version
     Update(UpdateArgs),
+    /// Pull template changes into an existing project
+    Upgrade(UpgradeArgs),
+    /// Show how a project drifted from its originating template
+    Diff(DiffArgs),
+    /// Manage the local template cache used by --offline
+    Cache(CacheArgs),
+    /// Check the environment, toolchain, configuration and cache for problems
+    Doctor(DoctorArgs),
 }
@@ -0,0 +1,226 @@
+//! Project provenance lock file
+//!
+//! Every project generated by `cza new` gets a `.cza.lock` file recording where it came
+//! from: the template key, repository, subfolder, requested revision and resolved commit,
+//! the cza version, a generation timestamp and the values passed to cargo-generate.
+//! `cza upgrade` rewrites the template source and records an upgrade timestamp.
+//!
+//! ## Example
+//!
+//! ```toml
+//! cza_version = "2.4.0"
+//! generated_at = "2025-01-01T12:00:00Z"
+//!
+//! [template]
+//! key = "noir-vite"
+//! repository = "https://github.com/sripwoud/cza-templates"
+//! subfolder = "noir-vite"
+//! commit = "0123456789abcdef0123456789abcdef01234567"
+//!
+//! [defines]
+//! author = "Jane Doe"
+//! project_name = "my-zk-app"
+//! ```
+
+use crate::template::{TemplateInfo, TemplateSource};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Name of the lock file written at the root of generated projects
+pub const LOCK_FILE_NAME: &str = ".cza.lock";
+
+/// Provenance information for a generated project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectLock {
+    /// Version of cza that generated the project
+    pub cza_version: String,
+    /// RFC 3339 timestamp of the generation
+    pub generated_at: String,
+    /// RFC 3339 timestamp of the last `cza upgrade`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upgraded_at: Option<String>,
+    /// Template the project was generated from
+    pub template: LockedTemplate,
+    /// Template values passed to cargo-generate
+    #[serde(default)]
+    pub defines: BTreeMap<String, String>,
+}
+
+/// Template source recorded in the lock file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedTemplate {
+    /// Template key in the registry
+    pub key: String,
+    /// Git repository URL (or `file://` path for local templates)
+    pub repository: String,
+    /// Subfolder path within the repository
+    #[serde(default)]
+    pub subfolder: String,
+    /// Revision requested with `--revision` or pinned in the registry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    /// Commit SHA the project was generated from, when it could be determined; never set
+    /// for a local template with uncommitted changes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+impl LockedTemplate {
+    /// Exact revision the project was generated from: the commit, or the requested revision
+    pub fn locked_revision(&self) -> Option<&str> {
+        self.commit.as_deref().or(self.revision.as_deref())
+    }
+
+    /// Template information for re-rendering the recorded template
+    pub fn template_info(&self) -> TemplateInfo {
+        TemplateInfo {
+            name: self.key.clone(),
+            description: format!("Template recorded in {}", LOCK_FILE_NAME),
+            repository: self.repository.clone(),
+            subfolder: self.subfolder.clone(),
+            frameworks: Vec::new(),
+            revision: self.revision.clone(),
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source: TemplateSource::default(),
+        }
+    }
+}
+
+impl ProjectLock {
+    /// Create a lock for a project generated now by the running cza version
+    pub fn new(template: LockedTemplate, defines: BTreeMap<String, String>) -> Self {
+        Self {
+            cza_version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at: now(),
+            upgraded_at: None,
+            template,
+            defines,
+        }
+    }
+
+    /// Record an upgrade to another template revision, keeping the generation time
+    pub fn upgrade(&mut self, template: LockedTemplate) {
+        self.template = template;
+        self.upgraded_at = Some(now());
+    }
+
+    /// Read the lock file from a project directory
+    pub fn read(project_dir: &Path) -> Result<Self> {
+        let path = project_dir.join(LOCK_FILE_NAME);
+        let contents = fs::read_to_string(&path).with_context(|| {
+            format!(
+                "Failed to read {}. Was this project generated by cza?",
+                path.display()
+            )
+        })?;
+        toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Write the lock file into a project directory
+    pub fn write(&self, project_dir: &Path) -> Result<()> {
+        let contents = toml::to_string_pretty(self).context("Failed to serialize lock file")?;
+        let contents = format!(
+            "# Generated by cza. Records the template this project was created from.\n{}",
+            contents
+        );
+        fs::write(project_dir.join(LOCK_FILE_NAME), contents)
+            .with_context(|| format!("Failed to write {}", LOCK_FILE_NAME))
+    }
+}
+
+fn now() -> String {
+    humantime::format_rfc3339_seconds(SystemTime::now()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_lock() -> ProjectLock {
+        let defines = BTreeMap::from([
+            ("project_name".to_string(), "my-app".to_string()),
+            ("author".to_string(), "Jane Doe".to_string()),
+        ]);
+        ProjectLock::new(
+            LockedTemplate {
+                key: "noir-vite".to_string(),
+                repository: "https://github.com/sripwoud/cza-templates".to_string(),
+                subfolder: "noir-vite".to_string(),
+                revision: None,
+                commit: Some("0123456789abcdef0123456789abcdef01234567".to_string()),
+            },
+            defines,
+        )
+    }
+
+    #[test]
+    fn test_new_lock_records_version_and_timestamp() {
+        let lock = sample_lock();
+        assert_eq!(lock.cza_version, env!("CARGO_PKG_VERSION"));
+        assert!(lock.generated_at.ends_with('Z'));
+        assert_eq!(lock.upgraded_at, None);
+    }
+
+    #[test]
+    fn test_upgrade_keeps_generation_time() {
+        let mut lock = sample_lock();
+        lock.generated_at = "2025-01-01T12:00:00Z".to_string();
+        let mut template = lock.template.clone();
+        template.commit = Some("fedcba9876543210fedcba9876543210fedcba98".to_string());
+
+        lock.upgrade(template.clone());
+        assert_eq!(lock.generated_at, "2025-01-01T12:00:00Z");
+        assert_eq!(lock.template, template);
+        assert!(lock.upgraded_at.unwrap().ends_with('Z'));
+    }
+
+    #[test]
+    fn test_write_and_read_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock = sample_lock();
+        lock.write(temp_dir.path()).unwrap();
+
+        let contents = fs::read_to_string(temp_dir.path().join(LOCK_FILE_NAME)).unwrap();
+        assert!(contents.contains("[template]"));
+        assert!(contents.contains("[defines]"));
+        assert!(!contents.contains("revision"));
+        assert!(!contents.contains("upgraded_at"));
+
+        let read = ProjectLock::read(temp_dir.path()).unwrap();
+        assert_eq!(read, lock);
+    }
+
+    #[test]
+    fn test_locked_revision_prefers_commit() {
+        let mut template = sample_lock().template;
+        template.revision = Some("main".to_string());
+        assert_eq!(
+            template.locked_revision(),
+            Some("0123456789abcdef0123456789abcdef01234567")
+        );
+
+        template.commit = None;
+        assert_eq!(template.locked_revision(), Some("main"));
+
+        let info = template.template_info();
+        assert_eq!(info.repository, template.repository);
+        assert_eq!(info.subfolder, template.subfolder);
+    }
+
+    #[test]
+    fn test_read_missing_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = ProjectLock::read(temp_dir.path());
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Was this project generated by cza?"));
+    }
+}
@@ -2,22 +2,30 @@ use anyhow::Result;
 use clap::Parser;
 use cza::{
     cmd::{
-        config::ConfigCommand, list::ListCommand, new::NewCommand, update::UpdateCommand, Execute,
+        cache::CacheCommand, config::ConfigCommand, diff::DiffCommand, doctor::DoctorCommand,
+        info::InfoCommand, list::ListCommand, new::NewCommand, search::SearchCommand,
+        update::UpdateCommand, upgrade::UpgradeCommand, Execute,
     },
     config::Config,
-    Cli, Command,
+    context, output, Cli, Command,
 };
 use log::debug;
 
-fn init_logging(cli_verbose: Option<bool>) -> Result<()> {
-    // Don't override if RUST_LOG is already set by user
-    if std::env::var("RUST_LOG").is_err() {
-        // Load config to get default verbose setting
-        let config = Config::load().unwrap_or_default();
+fn init_logging(config: Option<&Config>) -> Result<()> {
+    let verbose = context::verbose();
+    let quiet = context::quiet();
 
-        // Determine level: CLI flag > config > default
-        let verbose = cli_verbose.unwrap_or(config.development.verbose);
-        let level = if verbose { "debug" } else { "info" };
+    // Don't override RUST_LOG set by the user, unless -v or -q is given
+    if std::env::var("RUST_LOG").is_err() || verbose > 0 || quiet {
+        // Determine level: CLI flags > config > default
+        let config_verbose = config.is_some_and(|config| config.development.verbose);
+        let level = match verbose {
+            _ if quiet => "error",
+            0 if config_verbose => "debug",
+            0 => "info",
+            1 => "debug",
+            _ => "trace",
+        };
 
         std::env::set_var("RUST_LOG", format!("cza={}", level));
     }
@@ -28,12 +36,20 @@ fn init_logging(cli_verbose: Option<bool>) -> Result<()> {
 
 fn main() {
     let cli = Cli::parse();
+    context::init(cli.global.clone());
 
-    // Initialize logging - for now we don't have CLI verbose flags, so pass None
-    if let Err(e) = init_logging(None) {
+    // Resolve the configuration once for logging, output and commands. A broken config
+    // is reported by the commands loading it, `cza doctor` and `cza config reset` still run
+    let config = Config::load().ok();
+    if let Some(ref config) = config {
+        context::init_config(config.clone());
+    }
+
+    if let Err(e) = init_logging(config.as_ref()) {
         eprintln!("Failed to initialize logging: {}", e);
         std::process::exit(1);
     }
+    output::init();
 
     debug!("CLI arguments parsed: {:#?}", cli);
 
@@ -46,6 +62,14 @@ fn main() {
             debug!("Executing list command");
             ListCommand.execute(args)
         }
+        Command::Search(args) => {
+            debug!("Executing search command");
+            SearchCommand.execute(args)
+        }
+        Command::Info(args) => {
+            debug!("Executing info command");
+            InfoCommand.execute(args)
+        }
         Command::Config(args) => {
             debug!("Executing config command");
             ConfigCommand.execute(args)
@@ -54,5 +78,21 @@ fn main() {
             debug!("Executing update command");
             UpdateCommand.execute(args)
         }
+        Command::Upgrade(args) => {
+            debug!("Executing upgrade command");
+            UpgradeCommand.execute(args)
+        }
+        Command::Diff(args) => {
+            debug!("Executing diff command");
+            DiffCommand.execute(args)
+        }
+        Command::Cache(args) => {
+            debug!("Executing cache command");
+            CacheCommand.execute(args)
+        }
+        Command::Doctor(args) => {
+            debug!("Executing doctor command");
+            DoctorCommand.execute(args)
+        }
     }
 }
@@ -5,10 +5,28 @@
 //! - Emoji support with ASCII fallbacks
 //! - Consistent styling across all commands
 //! - Terminal-aware formatting
-//! - Configuration-aware color control
+//! - Configuration-aware color control, overridden by `--color`, `NO_COLOR` and
+//!   `CLICOLOR_FORCE`
+//! - A quiet mode (`-q`) printing only warnings, errors and command output
+//! - Spinners for long-running steps, see [`Progress`]
+//! - Newline-delimited JSON events with `--output json`
+//!
+//! In JSON mode every message is printed to stdout as one JSON object per line, with an
+//! RFC 3339 `timestamp` and an `event` name: `step_started`, `step_finished`, `info`,
+//! `warning`, `error` or `result`. Decorative output such as headers is dropped.
+
+mod progress;
 
+pub use progress::Progress;
+
+use crate::context;
+use crate::error::ErrorReport;
 use anyhow;
-use console::{style, Emoji, StyledObject, Term};
+use console::{style, Color, Emoji, StyledObject, Term};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::sync::OnceLock;
+use std::time::SystemTime;
 
 /// Success indicator emoji with ASCII fallback
 static SUCCESS_EMOJI: Emoji<'_, '_> = Emoji("✅", "[SUCCESS]");
@@ -31,19 +49,38 @@ static DIRECTORY_EMOJI: Emoji<'_, '_> = Emoji("📁", "[DIR]");
 /// Next steps indicator emoji with ASCII fallback
 static NEXT_EMOJI: Emoji<'_, '_> = Emoji("👉", "==>");
 
+/// How messages are printed, selected with the global `--output` flag
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Styled messages for humans
+    #[default]
+    Text,
+    /// Newline-delimited JSON events
+    Json,
+}
+
+/// When to use colors, selected with the global `--color` flag
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color terminals, unless NO_COLOR is set or CLICOLOR_FORCE forces colors
+    Auto,
+    Always,
+    Never,
+}
+
 /// Output manager for consistent CLI messaging
 pub struct Output {
     term: Term,
     color_enabled: bool,
+    /// Skip progress and informational messages
+    quiet: bool,
+    format: OutputFormat,
 }
 
 impl Output {
     /// Create a new output manager with default color settings
     pub fn new() -> Self {
-        Self {
-            term: Term::stdout(),
-            color_enabled: true, // Default to enabled
-        }
+        Self::with_color(true) // Default to enabled
     }
 
     /// Create a new output manager with specified color setting
@@ -51,9 +88,28 @@ impl Output {
         Self {
             term: Term::stdout(),
             color_enabled,
+            quiet: false,
+            format: OutputFormat::Text,
         }
     }
 
+    /// Create a new output manager printing JSON events
+    pub fn json() -> Self {
+        Self {
+            format: OutputFormat::Json,
+            ..Self::with_color(false)
+        }
+    }
+
+    /// Print an event line when in JSON mode, returning whether it did
+    fn emit(&self, event: &str, fields: Value) -> bool {
+        if self.format != OutputFormat::Json {
+            return false;
+        }
+        println!("{}", event_line(event, fields));
+        true
+    }
+
     /// Apply styling if colors are enabled, otherwise return plain text
     fn apply_style(&self, text: &str, styled: StyledObject<&str>) -> String {
         if self.color_enabled {
@@ -65,43 +121,79 @@ impl Output {
 
     /// Print a success message with green styling
     pub fn success(&self, message: &str) {
+        if self.quiet {
+            return;
+        }
+        if self.emit("step_finished", json!({ "message": message })) {
+            return;
+        }
         let styled_message = self.apply_style(message, style(message).green().bold());
         println!("{SUCCESS_EMOJI} {styled_message}");
     }
 
     /// Print an info message with blue styling
     pub fn info(&self, message: &str) {
+        if self.quiet {
+            return;
+        }
+        if self.emit("info", json!({ "message": message })) {
+            return;
+        }
         let styled_message = self.apply_style(message, style(message).blue());
         println!("{INFO_EMOJI} {styled_message}");
     }
 
     /// Print a warning message with yellow styling
     pub fn warning(&self, message: &str) {
+        if self.emit("warning", json!({ "message": message })) {
+            return;
+        }
         let styled_message = self.apply_style(message, style(message).yellow().bold());
         println!("{WARNING_EMOJI} {styled_message}");
     }
 
     /// Print an error message with red styling
     pub fn error(&self, message: &str) {
+        if self.emit("error", json!({ "message": message })) {
+            return;
+        }
         let styled_message = self.apply_style(message, style(message).red().bold());
         eprintln!("{ERROR_EMOJI} {styled_message}");
     }
 
     /// Print a step message with cyan styling (for progress indication)
     pub fn step(&self, message: &str) {
+        if self.quiet {
+            return;
+        }
+        if self.emit("step_started", json!({ "message": message })) {
+            return;
+        }
         let styled_message = self.apply_style(message, style(message).cyan());
         println!("{STEP_EMOJI} {styled_message}");
     }
 
     /// Print a directory path with consistent styling
     pub fn directory(&self, path: &str) {
+        if self.quiet {
+            return;
+        }
+        if self.emit(
+            "info",
+            json!({ "message": format!("Location: {}", path), "path": path }),
+        ) {
+            return;
+        }
         let styled_path = self.apply_style(path, style(path).magenta().bold());
         println!("{DIRECTORY_EMOJI} Location: {styled_path}");
     }
 
     /// Print next steps with consistent styling
     pub fn next_steps(&self, steps: &[&str]) {
-        if steps.is_empty() {
+        if steps.is_empty()
+            || self.quiet
+            || self.emit("info", json!({ "message": "Next steps", "steps": steps }))
+        {
             return;
         }
 
@@ -117,6 +209,15 @@ impl Output {
 
     /// Print a command suggestion with consistent styling
     pub fn command_example(&self, description: &str, command: &str) {
+        if self.quiet {
+            return;
+        }
+        if self.emit(
+            "info",
+            json!({ "message": description, "command": command }),
+        ) {
+            return;
+        }
         let styled_desc = self.apply_style(description, style(description).dim());
         let styled_command = self.apply_style(command, style(command).green().bold());
         println!("  {styled_desc}: {styled_command}");
@@ -124,6 +225,9 @@ impl Output {
 
     /// Print a header for sections
     pub fn header(&self, title: &str) {
+        if self.format == OutputFormat::Json {
+            return;
+        }
         println!();
         let styled_title = self.apply_style(title, style(title).bold().underlined());
         println!("{styled_title}");
@@ -132,17 +236,32 @@ impl Output {
 
     /// Print a plain message without styling (for regular content)
     pub fn plain(&self, message: &str) {
+        if self.format == OutputFormat::Json {
+            if !message.is_empty() {
+                self.emit("info", json!({ "message": message }));
+            }
+            return;
+        }
         println!("{message}");
     }
 
     /// Print a styled key-value pair
     pub fn key_value(&self, key: &str, value: &str) {
+        if self.emit(
+            "info",
+            json!({ "message": format!("{}: {}", key, value), "key": key, "value": value }),
+        ) {
+            return;
+        }
         let styled_key = self.apply_style(key, style(key).bold());
         println!("   {styled_key}: {value}");
     }
 
     /// Print a template item with consistent styling
     pub fn template_item(&self, name: &str, description: &str) {
+        if self.emit("info", json!({ "message": format!("{} - {}", name, description), "template": name, "description": description })) {
+            return;
+        }
         let styled_name = self.apply_style(name, style(name).green().bold());
         let styled_desc = self.apply_style(description, style(description).dim());
         println!("  {styled_name} - {styled_desc}");
@@ -157,6 +276,17 @@ impl Output {
         frameworks: &[String],
         repository: &str,
     ) {
+        let fields = json!({
+            "message": format!("{} - {}", key, description),
+            "template": key,
+            "name": name,
+            "description": description,
+            "frameworks": frameworks,
+            "repository": repository,
+        });
+        if self.emit("info", fields) {
+            return;
+        }
         let styled_key = self.apply_style(key, style(key).green().bold());
         println!("{STEP_EMOJI} {styled_key}");
         self.key_value("Name", name);
@@ -166,8 +296,35 @@ impl Output {
         println!();
     }
 
+    /// Print a diagnostic row: a colored status label, the check name and its result,
+    /// followed by a fix hint when given
+    pub fn check(&self, label: &str, color: Color, name: &str, detail: &str, hint: Option<&str>) {
+        let fields = json!({
+            "message": format!("{}: {}", name, detail),
+            "status": label.trim().to_lowercase(),
+            "check": name,
+            "hint": hint,
+        });
+        if self.emit("info", fields) {
+            return;
+        }
+        let padded_label = format!("{label:<4}");
+        let styled_label =
+            self.apply_style(&padded_label, style(padded_label.as_str()).fg(color).bold());
+        let padded_name = format!("{name:<14}");
+        let styled_name = self.apply_style(&padded_name, style(padded_name.as_str()).bold());
+        println!("  {styled_label}  {styled_name} {detail}");
+        if let Some(hint) = hint {
+            let styled_hint = self.apply_style(hint, style(hint).dim());
+            println!("{:23}{NEXT_EMOJI} {styled_hint}", "");
+        }
+    }
+
     /// Clear the screen if supported
     pub fn clear(&self) {
+        if self.format == OutputFormat::Json {
+            return;
+        }
         let _ = self.term.clear_screen();
     }
 }
@@ -178,12 +335,100 @@ impl Default for Output {
     }
 }
 
-/// Create an output instance based on current config
+/// A line of `--output json`
+#[derive(Serialize)]
+struct Event<'a> {
+    timestamp: String,
+    event: &'a str,
+    #[serde(flatten)]
+    fields: Value,
+}
+
+/// One JSON event line: a timestamp, the event name and the event fields
+fn event_line(event: &str, fields: Value) -> String {
+    let event = Event {
+        timestamp: humantime::format_rfc3339_millis(SystemTime::now()).to_string(),
+        event,
+        fields,
+    };
+    serde_json::to_string(&event).unwrap_or_default()
+}
+
+/// Whether `--output json` was given
+pub fn is_json() -> bool {
+    context::output_format() == OutputFormat::Json
+}
+
+/// Output settings, resolved from the global flags and the configuration
+#[derive(Debug, Clone, Copy)]
+struct Settings {
+    color_enabled: bool,
+    quiet: bool,
+    format: OutputFormat,
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+impl Settings {
+    fn resolve() -> Self {
+        // Fallback to colors if config can't be loaded
+        let config_color = context::config()
+            .map(|config| config.development.color)
+            .unwrap_or(true);
+        let format = context::output_format();
+        Self {
+            color_enabled: format == OutputFormat::Text
+                && use_color(
+                    context::color(),
+                    config_color,
+                    |var| std::env::var(var).ok(),
+                    Term::stdout().is_term(),
+                ),
+            quiet: context::quiet(),
+            format,
+        }
+    }
+}
+
+/// Whether to use colors: `--color always|never`, else `NO_COLOR` and `CLICOLOR_FORCE`,
+/// else `development.color` (ignored with `--color auto`) for terminals
+fn use_color(
+    choice: Option<ColorChoice>,
+    config_color: bool,
+    env: impl Fn(&str) -> Option<String>,
+    is_terminal: bool,
+) -> bool {
+    let config_color = match choice {
+        Some(ColorChoice::Always) => return true,
+        Some(ColorChoice::Never) => return false,
+        Some(ColorChoice::Auto) => true,
+        None => config_color,
+    };
+    if env("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        return false;
+    }
+    if env("CLICOLOR_FORCE").is_some_and(|value| !value.is_empty() && value != "0") {
+        return true;
+    }
+    config_color && is_terminal
+}
+
+/// Resolve the output settings once for this process, after the global flags and the
+/// configuration, and apply the color choice to every styled string
+pub fn init() {
+    let settings = *SETTINGS.get_or_init(Settings::resolve);
+    console::set_colors_enabled(settings.color_enabled);
+    console::set_colors_enabled_stderr(settings.color_enabled);
+}
+
+/// Create an output instance from the settings resolved by [`init`]
 fn get_output() -> Output {
-    use crate::config::Config;
-    match Config::load() {
-        Ok(config) => Output::with_color(config.development.color),
-        Err(_) => Output::new(), // Fallback to default if config can't be loaded
+    let settings = SETTINGS.get().copied().unwrap_or_else(Settings::resolve);
+    Output {
+        term: Term::stdout(),
+        color_enabled: settings.color_enabled,
+        quiet: settings.quiet,
+        format: settings.format,
     }
 }
 
@@ -253,40 +498,73 @@ pub fn template_detailed(
     get_output().template_detailed(key, name, description, frameworks, repository);
 }
 
+/// Convenience function for diagnostic rows
+pub fn check(label: &str, color: Color, name: &str, detail: &str, hint: Option<&str>) {
+    get_output().check(label, color, name, detail, hint);
+}
+
+/// Emit the result of a command as a `result` event, in JSON mode only
+pub fn result<T: Serialize + ?Sized>(value: &T) {
+    if let Ok(value) = serde_json::to_value(value) {
+        get_output().emit("result", json!({ "result": value }));
+    }
+}
+
+/// Print the JSON output of a command: pretty-printed for `--json`, or as a `result`
+/// event with `--output json`
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> anyhow::Result<()> {
+    if is_json() {
+        result(value);
+    } else {
+        println!("{}", serde_json::to_string_pretty(value)?);
+    }
+    Ok(())
+}
+
 /// Format and display anyhow errors using our consistent output system
+///
+/// The message goes to stderr, followed by the suggestions and hint of a
+/// [`CzaError`](crate::error::CzaError). With `--output json` the report is a single
+/// `error` event instead.
 pub fn format_error(err: &anyhow::Error) {
-    let output = get_output();
-    let error_msg = err.to_string();
-
-    // Handle specific error patterns with enhanced formatting
-    if error_msg.contains("not found. Use 'cza list'") {
-        // Split the template not found error for better formatting
-        if let Some(template_part) = error_msg.split('.').next() {
-            output.error(template_part);
-            output.info("Use 'cza list' to see available templates.");
-        } else {
-            output.error(&error_msg);
+    // The hint and suggestions are part of the error, so quiet mode keeps them
+    let output = Output {
+        quiet: false,
+        ..get_output()
+    };
+    let report = ErrorReport::new(err);
+    if let Ok(Value::Object(fields)) = serde_json::to_value(&report) {
+        if output.emit("error", Value::Object(fields)) {
+            return;
         }
-    } else if error_msg.contains("already exists") {
-        // Handle directory exists errors
-        output.error(&error_msg);
-        output.info("Choose a different project name or remove the existing directory.");
-    } else if error_msg.contains("Project name") {
-        // Handle project name validation errors
-        output.error(&error_msg);
-        output.info(
-            "Project names can only contain alphanumeric characters, hyphens, and underscores.",
-        );
-    } else {
-        // Default error formatting
-        output.error(&error_msg);
+    }
+
+    output.error(&report.message);
+    if let Some(did_you_mean) = report.did_you_mean() {
+        output.info(&did_you_mean);
+    }
+    if let Some(ref hint) = report.hint {
+        output.info(hint);
+    }
+}
+
+/// Print an error as a JSON object on stderr, for commands given `--json`
+pub fn format_error_json(err: &anyhow::Error) {
+    if is_json() {
+        return format_error(err);
+    }
+    let report = ErrorReport::new(err);
+    match serde_json::to_string_pretty(&serde_json::json!({ "error": report })) {
+        Ok(json) => eprintln!("{}", json),
+        Err(_) => format_error(err),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use anyhow;
+    use crate::error::CzaError;
+    use crate::suggest::{NameKind, UnknownName};
 
     #[test]
     fn test_output_new() {
@@ -413,23 +691,107 @@ mod tests {
         template_detailed("key", "name", "desc", &["framework".to_string()], "repo");
     }
 
+    #[test]
+    fn test_use_color() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |var: &str| {
+                vars.iter()
+                    .find(|(name, _)| *name == var)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        // Terminals are colored unless the config disables it
+        assert!(use_color(None, true, env(&[]), true));
+        assert!(!use_color(None, true, env(&[]), false));
+        assert!(!use_color(None, false, env(&[]), true));
+        assert!(use_color(Some(ColorChoice::Auto), false, env(&[]), true));
+
+        assert!(!use_color(None, true, env(&[("NO_COLOR", "1")]), true));
+        assert!(use_color(None, true, env(&[("NO_COLOR", "")]), true));
+        assert!(use_color(
+            None,
+            false,
+            env(&[("CLICOLOR_FORCE", "1")]),
+            false
+        ));
+        assert!(!use_color(
+            None,
+            true,
+            env(&[("CLICOLOR_FORCE", "0")]),
+            false
+        ));
+
+        // Flags win over the environment
+        let no_color = env(&[("NO_COLOR", "1")]);
+        assert!(use_color(Some(ColorChoice::Always), false, no_color, false));
+        let force = env(&[("CLICOLOR_FORCE", "1")]);
+        assert!(!use_color(Some(ColorChoice::Never), true, force, true));
+    }
+
+    #[test]
+    fn test_quiet() {
+        let output = Output {
+            quiet: true,
+            ..Output::with_color(false)
+        };
+        output.success("Hidden");
+        output.step("Hidden");
+        output.next_steps(&["Hidden"]);
+        output.warning("Shown");
+    }
+
+    #[test]
+    fn test_event_line() {
+        let line = event_line("step_started", json!({ "message": "Generating" }));
+        assert!(!line.contains('\n'));
+        assert!(line.starts_with("{\"timestamp\":"));
+        let event: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(event["event"], "step_started");
+        assert_eq!(event["message"], "Generating");
+        let timestamp = event["timestamp"].as_str().unwrap();
+        assert!(humantime::parse_rfc3339(timestamp).is_ok());
+    }
+
+    #[test]
+    fn test_output_json() {
+        let output = Output::json();
+        assert!(output.emit("info", json!({ "message": "Test" })));
+        assert!(!Output::new().emit("info", json!({ "message": "Test" })));
+        output.success("Test success");
+        output.step("Test step");
+        output.header("Skipped");
+        output.plain("");
+        output.next_steps(&["Step 1"]);
+        output.check("ok", Color::Green, "git", "2.43.0", None);
+        output.clear();
+    }
+
     #[test]
     fn test_format_error_template_not_found() {
-        let err = anyhow::anyhow!(
-            "Template 'invalid' not found. Use 'cza list' to see available templates."
-        );
+        let err = anyhow::Error::from(CzaError::from(UnknownName::new(
+            NameKind::Template,
+            "noir-vit",
+            ["noir-vite"],
+        )));
         format_error(&err);
+        format_error_json(&err);
     }
 
     #[test]
     fn test_format_error_already_exists() {
-        let err = anyhow::anyhow!("Directory already exists");
+        let err = anyhow::Error::from(CzaError::DestinationExists {
+            path: "my-app".into(),
+        });
         format_error(&err);
     }
 
     #[test]
     fn test_format_error_project_name() {
-        let err = anyhow::anyhow!("Project name contains invalid characters");
+        let err = anyhow::Error::from(CzaError::InvalidProjectName {
+            name: "1app".to_string(),
+            reason: "must start with a letter",
+        });
         format_error(&err);
     }
 
@@ -437,12 +799,7 @@ mod tests {
     fn test_format_error_generic() {
         let err = anyhow::anyhow!("Generic error message");
         format_error(&err);
-    }
-
-    #[test]
-    fn test_format_error_template_not_found_no_split() {
-        let err = anyhow::anyhow!("not found. Use 'cza list'");
-        format_error(&err);
+        format_error_json(&err);
     }
 
     #[test]
@@ -0,0 +1,183 @@
+//! Progress of long-running steps
+//!
+//! On a terminal a [`Progress`] is a spinner showing the elapsed time, with the last lines
+//! printed by the step's subprocess in a region below it. The region collapses when the
+//! step ends, and its contents are printed again if the step fails. Elsewhere the step
+//! falls back to plain lines, and with `--output json` to `step_started` and
+//! `step_finished` events, the subprocess output going to stderr.
+
+use super::{get_output, style, Output, OutputFormat};
+use console::Term;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Subprocess lines shown below the spinner
+const TAIL_LINES: usize = 5;
+
+/// Subprocess lines kept to report a failure
+const CAPTURED_LINES: usize = 20;
+
+/// A long-running step, finished with [`Progress::finish`] or [`Progress::fail`]
+///
+/// Dropping an unfinished step clears its spinner, e.g. when an error is returned early.
+pub struct Progress {
+    output: Output,
+    bar: Option<ProgressBar>,
+    started: Instant,
+    captured: VecDeque<String>,
+}
+
+impl Progress {
+    /// Start a step, described by `message`
+    pub fn start(message: &str) -> Self {
+        let output = get_output();
+        let spinner =
+            output.format == OutputFormat::Text && !output.quiet && Term::stderr().is_term();
+        let bar = if spinner {
+            let bar = ProgressBar::new_spinner().with_style(
+                ProgressStyle::with_template("{spinner:.cyan} {prefix:.cyan} {elapsed:.dim}{msg}")
+                    .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+            );
+            bar.set_prefix(message.to_string());
+            bar.enable_steady_tick(Duration::from_millis(100));
+            Some(bar)
+        } else {
+            output.step(message);
+            None
+        };
+        Self {
+            output,
+            bar,
+            started: Instant::now(),
+            captured: VecDeque::new(),
+        }
+    }
+
+    /// Report a line printed by the step's subprocess
+    pub fn line(&mut self, line: &str) {
+        let line = line.trim_end();
+        if self.captured.len() == CAPTURED_LINES {
+            self.captured.pop_front();
+        }
+        self.captured.push_back(line.to_string());
+
+        match self.bar {
+            Some(ref bar) => {
+                let width = usize::from(Term::stderr().size().1).saturating_sub(6);
+                let tail: String = self
+                    .captured
+                    .iter()
+                    .skip(self.captured.len().saturating_sub(TAIL_LINES))
+                    .map(|line| {
+                        let line = console::truncate_str(line, width, "…");
+                        format!("\n  {} {}", style("│").dim(), style(line).dim())
+                    })
+                    .collect();
+                bar.set_message(tail);
+            }
+            // Keep stdout for the event stream
+            None if self.output.format == OutputFormat::Json => eprintln!("{}", line),
+            None if self.output.quiet => {}
+            None => self.output.plain(&format!("    {}", line)),
+        }
+    }
+
+    /// Run `f` with the spinner hidden, for work that may prompt on the terminal
+    pub fn suspend<T>(&self, f: impl FnOnce() -> T) -> T {
+        match self.bar {
+            Some(ref bar) => bar.suspend(f),
+            None => f(),
+        }
+    }
+
+    /// End the step successfully, reporting `message` with the elapsed time
+    pub fn finish(mut self, message: &str) {
+        let elapsed = self.clear();
+        if !self.output.emit(
+            "step_finished",
+            json!({ "message": message, "elapsed_ms": elapsed.as_millis() }),
+        ) {
+            self.output
+                .success(&format!("{} ({})", message, format_elapsed(elapsed)));
+        }
+    }
+
+    /// End the step with a warning, followed by the last lines of the subprocess output
+    /// when the spinner hid them
+    pub fn fail(mut self, message: &str) {
+        let spinner = self.bar.is_some();
+        let elapsed = self.clear();
+        let fields = json!({
+            "message": message,
+            "elapsed_ms": elapsed.as_millis(),
+            "output": self.captured,
+        });
+        if self.output.emit("warning", fields) {
+            return;
+        }
+        self.output
+            .warning(&format!("{} ({})", message, format_elapsed(elapsed)));
+        if spinner || self.output.quiet {
+            for line in &self.captured {
+                self.output.plain(&format!("    {}", line));
+            }
+        }
+    }
+
+    /// Remove the spinner and its output region, returning the elapsed time
+    fn clear(&mut self) -> Duration {
+        if let Some(bar) = self.bar.take() {
+            bar.finish_and_clear();
+        }
+        self.started.elapsed()
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// Short human-readable duration, e.g. `0.4s`, `12.3s` or `2m 05s`
+fn format_elapsed(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    if seconds < 60 {
+        format!("{:.1}s", elapsed.as_secs_f64())
+    } else {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_millis(420)), "0.4s");
+        assert_eq!(format_elapsed(Duration::from_millis(12_345)), "12.3s");
+        assert_eq!(format_elapsed(Duration::from_secs(125)), "2m 05s");
+    }
+
+    #[test]
+    fn test_progress_captures_lines() {
+        let mut progress = Progress::start("Installing");
+        for index in 0..CAPTURED_LINES + 5 {
+            progress.line(&format!("line {}\n", index));
+        }
+        assert_eq!(progress.captured.len(), CAPTURED_LINES);
+        assert_eq!(progress.captured.front().unwrap(), "line 5");
+        assert_eq!(
+            progress.captured.back().unwrap(),
+            &format!("line {}", CAPTURED_LINES + 4)
+        );
+        progress.fail("Install failed");
+
+        let progress = Progress::start("Generating");
+        assert_eq!(progress.suspend(|| 42), 42);
+        progress.finish("Generated");
+    }
+}
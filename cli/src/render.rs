@@ -0,0 +1,573 @@
+//! Rendering templates into scratch directories
+//!
+//! Commands that need to know what a template produces without touching the user's
+//! project (upgrade, diff, dry-run previews) render it into a temporary directory with
+//! cargo-generate and compare file trees.
+//!
+//! This module provides helpers to:
+//!
+//! - Render a template with a fixed set of values, without prompting or initializing git
+//! - Read a rendered file tree into memory
+//! - Read a project working tree, honoring `.gitignore` and extra ignore globs
+//! - Produce unified diffs between file versions
+//! - Display file trees with their sizes
+
+use crate::{
+    cache, context,
+    template::{details::TemplateDetails, TemplateInfo},
+};
+use anyhow::{anyhow, Context, Result};
+use cargo_generate::{generate, GenerateArgs, TemplatePath, Vcs};
+use globset::GlobSet;
+use ignore::WalkBuilder;
+use log::debug;
+use similar::TextDiff;
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+/// In-memory file tree keyed by path relative to its root
+pub type FileTree = BTreeMap<PathBuf, Vec<u8>>;
+
+/// Directory names never included when reading file trees
+const SKIPPED_DIRS: &[&str] = &[".git"];
+
+/// Author used when neither the values nor the environment name one
+pub const FALLBACK_AUTHOR: &str = "Developer";
+
+/// Environment variables cargo-generate reads the current user from, besides git config
+const AUTHOR_VARIABLES: &[&str] = &[
+    "CARGO_NAME",
+    "GIT_AUTHOR_NAME",
+    "GIT_COMMITTER_NAME",
+    "USER",
+    "USERNAME",
+    "NAME",
+];
+
+/// Let cargo-generate determine an author even when the environment names no user
+///
+/// cargo-generate looks up the current user for every template and fails when none of
+/// its variables are set, as in minimal containers. In that case `CARGO_NAME` is set to
+/// `author` for the rest of the process.
+pub fn ensure_author(author: &str) {
+    static AUTHOR: Once = Once::new();
+    AUTHOR.call_once(|| {
+        if AUTHOR_VARIABLES
+            .iter()
+            .all(|variable| env::var_os(variable).is_none())
+        {
+            debug!("No user set in the environment, using {} as author", author);
+            env::set_var("CARGO_NAME", author);
+        }
+    });
+}
+
+/// Render a template into `destination` using `defines` as template values
+///
+/// Rendering is non-interactive: placeholders without a value fall back to their
+/// defaults. Returns the directory containing the rendered project.
+pub fn render_template(
+    template_path: TemplatePath,
+    defines: &BTreeMap<String, String>,
+    destination: &Path,
+) -> Result<PathBuf> {
+    let project_name = defines
+        .get("project_name")
+        .cloned()
+        .ok_or_else(|| anyhow!("Missing project_name value for rendering the template"))?;
+    let mut defines = defines.clone();
+    let author = defines
+        .entry("author".to_string())
+        .or_insert_with(|| FALLBACK_AUTHOR.to_string());
+    ensure_author(author);
+    check_values(&template_path, &defines)
+        .map_err(|reason| anyhow!("Failed to render template: {}", reason))?;
+
+    let generate_args = GenerateArgs {
+        template_path,
+        name: Some(project_name),
+        define: defines
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect(),
+        destination: Some(destination.to_path_buf()),
+        silent: true,
+        vcs: Some(Vcs::None),
+        ..Default::default()
+    };
+
+    debug!("Rendering template into {}", destination.display());
+    generate(generate_args)
+        .map_err(|e| anyhow!("Failed to render template: {}", describe_generate_error(&e)))
+}
+
+/// Check `defines` against the placeholders of the template at `template_path`
+///
+/// Catches values a placeholder's type, `choices` or `regex` rejects before cargo-generate
+/// runs. Templates that aren't on disk or whose `cargo-generate.toml` can't be read are
+/// left to cargo-generate. Returns the reason a value is rejected.
+pub fn check_values(
+    template_path: &TemplatePath,
+    defines: &BTreeMap<String, String>,
+) -> Result<(), String> {
+    let Some(ref dir) = template_path.path else {
+        return Ok(());
+    };
+    let details = match TemplateDetails::read(Path::new(dir)) {
+        Ok(details) => details,
+        Err(e) => {
+            debug!("Not checking template values: {:#}", e);
+            return Ok(());
+        }
+    };
+
+    for placeholder in &details.placeholders {
+        let Some(value) = defines.get(&placeholder.name) else {
+            continue;
+        };
+        if let Some(problem) = placeholder.check(value) {
+            return Err(invalid_value(
+                &format!("\"{}\"", value),
+                &placeholder.name,
+                Some(&problem),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Whether cargo-generate may prompt when generating the template at `template_path`
+/// with `defines`
+///
+/// It asks for every placeholder without a value, and hooks can ask for anything.
+/// Templates that aren't on disk or whose `cargo-generate.toml` can't be read may prompt.
+pub fn may_prompt(template_path: &TemplatePath, defines: &BTreeMap<String, String>) -> bool {
+    let Some(ref dir) = template_path.path else {
+        return true;
+    };
+    match TemplateDetails::read(Path::new(dir)) {
+        Ok(details) => {
+            !details.hooks.is_empty()
+                || details
+                    .placeholders
+                    .iter()
+                    .any(|placeholder| !defines.contains_key(&placeholder.name))
+        }
+        Err(_) => true,
+    }
+}
+
+/// Plain-text description of a cargo-generate error
+///
+/// Strips styling and explains template values rejected by a placeholder's `regex` or
+/// `choices`, which cargo-generate reports as `Sorry, "VALUE" is not a valid value for KEY`.
+/// Values are normally caught earlier by [`check_values`].
+pub fn describe_generate_error(e: &anyhow::Error) -> String {
+    let message = console::strip_ansi_codes(&format!("{:#}", e)).to_string();
+    let message = message.trim_start_matches(|c: char| !c.is_alphanumeric());
+
+    match message
+        .strip_prefix("Sorry, ")
+        .and_then(|rest| rest.split_once(" is not a valid value for "))
+    {
+        Some((value, placeholder)) => invalid_value(value, placeholder.trim(), None),
+        None => message.to_string(),
+    }
+}
+
+fn invalid_value(value: &str, placeholder: &str, problem: Option<&str>) -> String {
+    let problem = problem.map(|problem| format!(" ({})", problem));
+    format!(
+        "Invalid value {} for template placeholder '{}'{}. Check the values passed with --define, --values-file or [template_values] in config",
+        value,
+        placeholder,
+        problem.unwrap_or_default()
+    )
+}
+
+/// Render `template_info` at `revision` (or its latest state) into a new `destination`
+pub fn render_revision(
+    template_info: &TemplateInfo,
+    revision: Option<&str>,
+    defines: &BTreeMap<String, String>,
+    destination: &Path,
+) -> Result<PathBuf> {
+    fs::create_dir_all(destination)
+        .with_context(|| format!("Failed to create {}", destination.display()))?;
+    let prepared = cache::prepare(template_info, revision, context::offline())?;
+    render_template(prepared.template_path, defines, destination)
+        .map_err(|e| anyhow!("{:#} (revision {})", e, revision.unwrap_or("latest")))
+}
+
+/// Read every file below `root` into memory, skipping `.git` directories
+pub fn read_tree(root: &Path) -> Result<FileTree> {
+    let mut tree = FileTree::new();
+    read_tree_into(root, root, &mut tree)?;
+    Ok(tree)
+}
+
+fn read_tree_into(root: &Path, dir: &Path, tree: &mut FileTree) -> Result<()> {
+    for entry in
+        fs::read_dir(dir).with_context(|| format!("Failed to read directory {}", dir.display()))?
+    {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            if SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()) {
+                continue;
+            }
+            read_tree_into(root, &path, tree)?;
+        } else if file_type.is_file() {
+            let contents =
+                fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+            tree.insert(relative, contents);
+        }
+    }
+    Ok(())
+}
+
+/// Read a working tree like git would see it
+///
+/// Honors `.gitignore`/`.ignore` files (whether or not the directory is a git repository),
+/// includes hidden files, skips `.git` and any path matching `ignores`.
+pub fn read_worktree(root: &Path, ignores: &GlobSet) -> Result<FileTree> {
+    let mut tree = FileTree::new();
+    let walker = WalkBuilder::new(root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| !SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()))
+        .build();
+
+    for entry in walker {
+        let entry = entry.with_context(|| format!("Failed to walk {}", root.display()))?;
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        let path = entry.path();
+        let relative = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+        if ignores.is_match(&relative) {
+            continue;
+        }
+        let contents =
+            fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        tree.insert(relative, contents);
+    }
+    Ok(tree)
+}
+
+/// Sorted union of the paths of two file trees
+pub fn union_paths<'a>(a: &'a FileTree, b: &'a FileTree) -> BTreeSet<&'a PathBuf> {
+    a.keys().chain(b.keys()).collect()
+}
+
+/// Unified diff turning `old` into `new` for `path`
+///
+/// Missing sides are diffed against an empty file, using `/dev/null` as the header the
+/// way `git diff` does. Binary contents produce a one-line notice.
+pub fn unified_diff(path: &Path, old: Option<&[u8]>, new: Option<&[u8]>) -> String {
+    let display = path.display().to_string();
+    let old_header = old.map_or("/dev/null".to_string(), |_| format!("a/{}", display));
+    let new_header = new.map_or("/dev/null".to_string(), |_| format!("b/{}", display));
+
+    let (Ok(old_text), Ok(new_text)) = (
+        std::str::from_utf8(old.unwrap_or_default()),
+        std::str::from_utf8(new.unwrap_or_default()),
+    ) else {
+        return format!("Binary files {} and {} differ\n", old_header, new_header);
+    };
+
+    TextDiff::from_lines(old_text, new_text)
+        .unified_diff()
+        .header(&old_header, &new_header)
+        .to_string()
+}
+
+/// Human-readable size of a file, e.g. `512 B` or `1.5 KiB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Lines drawing `tree` like the `tree` command, with file sizes
+///
+/// ```text
+/// ├── README.md (12 B)
+/// └── src/
+///     └── main.nr (14 B)
+/// ```
+pub fn tree_lines(tree: &FileTree) -> Vec<String> {
+    #[derive(Default)]
+    struct Dir<'a> {
+        dirs: BTreeMap<String, Dir<'a>>,
+        files: BTreeMap<String, &'a [u8]>,
+    }
+
+    fn push_lines(dir: &Dir, prefix: &str, lines: &mut Vec<String>) {
+        let entries = dir.dirs.len() + dir.files.len();
+        let mut index = 0;
+        let connector = |index: usize| {
+            if index + 1 == entries {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            }
+        };
+
+        for (name, child) in &dir.dirs {
+            let (branch, indent) = connector(index);
+            lines.push(format!("{}{}{}/", prefix, branch, name));
+            push_lines(child, &format!("{}{}", prefix, indent), lines);
+            index += 1;
+        }
+        for (name, contents) in &dir.files {
+            let (branch, _) = connector(index);
+            lines.push(format!(
+                "{}{}{} ({})",
+                prefix,
+                branch,
+                name,
+                format_size(contents.len() as u64)
+            ));
+            index += 1;
+        }
+    }
+
+    let mut root = Dir::default();
+    for (path, contents) in tree {
+        let mut components: Vec<String> = path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+        let Some(file_name) = components.pop() else {
+            continue;
+        };
+        let dir = components
+            .into_iter()
+            .fold(&mut root, |dir, name| dir.dirs.entry(name).or_default());
+        dir.files.insert(file_name, contents);
+    }
+
+    let mut lines = Vec::new();
+    push_lines(&root, "", &mut lines);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_tree_skips_git() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join("README.md"), "readme").unwrap();
+        fs::write(root.join("src").join("main.nr"), "fn main() {}").unwrap();
+        fs::write(root.join(".git").join("HEAD"), "ref").unwrap();
+
+        let tree = read_tree(root).unwrap();
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[Path::new("README.md")], b"readme");
+        assert!(tree.contains_key(Path::new("src/main.nr")));
+    }
+
+    #[test]
+    fn test_read_worktree_honors_ignores() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("node_modules").join("dep")).unwrap();
+        fs::create_dir_all(root.join("web")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join(".gitignore"), "node_modules/\n").unwrap();
+        fs::write(root.join("node_modules").join("dep").join("index.js"), "").unwrap();
+        fs::write(root.join("web").join("app.ts"), "app").unwrap();
+        fs::write(root.join(".git").join("HEAD"), "ref").unwrap();
+        fs::write(root.join("README.md"), "readme").unwrap();
+
+        let ignores = globset::GlobSetBuilder::new()
+            .add(globset::Glob::new("web/**").unwrap())
+            .build()
+            .unwrap();
+        let tree = read_worktree(root, &ignores).unwrap();
+        let paths: Vec<_> = tree
+            .keys()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        assert_eq!(paths, vec![".gitignore", "README.md"]);
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let diff = unified_diff(
+            Path::new("README.md"),
+            Some(b"hello\nworld\n"),
+            Some(b"hello\nthere\n"),
+        );
+        assert!(diff.contains("--- a/README.md"));
+        assert!(diff.contains("+++ b/README.md"));
+        assert!(diff.contains("-world"));
+        assert!(diff.contains("+there"));
+
+        let diff = unified_diff(Path::new("new.txt"), None, Some(b"added\n"));
+        assert!(diff.contains("--- /dev/null"));
+        assert!(diff.contains("+added"));
+    }
+
+    #[test]
+    fn test_unified_diff_binary() {
+        let diff = unified_diff(Path::new("logo.png"), Some(&[0xff, 0xfe]), Some(&[0x00]));
+        assert_eq!(diff, "Binary files a/logo.png and b/logo.png differ\n");
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+    }
+
+    #[test]
+    fn test_tree_lines() {
+        let tree = FileTree::from([
+            (PathBuf::from("README.md"), b"readme".to_vec()),
+            (PathBuf::from("src/main.nr"), b"fn main() {}".to_vec()),
+            (PathBuf::from("src/lib/utils.nr"), Vec::new()),
+        ]);
+
+        assert_eq!(
+            tree_lines(&tree),
+            vec![
+                "├── src/",
+                "│   ├── lib/",
+                "│   │   └── utils.nr (0 B)",
+                "│   └── main.nr (12 B)",
+                "└── README.md (6 B)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_describe_generate_error() {
+        let e = anyhow!(
+            "⚠️ {} \"{}\" {}",
+            console::style("Sorry,").bold().red(),
+            console::style("ultra").bold().yellow(),
+            console::style("is not a valid value for backend").red()
+        );
+        assert_eq!(
+            describe_generate_error(&e),
+            "Invalid value \"ultra\" for template placeholder 'backend'. Check the values passed with --define, --values-file or [template_values] in config"
+        );
+
+        let e = anyhow!("could not determine the current user");
+        assert_eq!(
+            describe_generate_error(&e),
+            "could not determine the current user"
+        );
+    }
+
+    #[test]
+    fn test_check_values() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("cargo-generate.toml"),
+            "[placeholders.backend]\ntype = \"string\"\nprompt = \"Backend?\"\nchoices = [\"barretenberg\", \"plonky2\"]\n",
+        )
+        .unwrap();
+        let template_path = TemplatePath {
+            path: Some(temp_dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        };
+
+        let mut defines = BTreeMap::from([("project_name".to_string(), "app".to_string())]);
+        assert_eq!(check_values(&template_path, &defines), Ok(()));
+        defines.insert("backend".to_string(), "plonky2".to_string());
+        assert_eq!(check_values(&template_path, &defines), Ok(()));
+
+        defines.insert("backend".to_string(), "ultra".to_string());
+        assert_eq!(
+            check_values(&template_path, &defines),
+            Err("Invalid value \"ultra\" for template placeholder 'backend' (expected one of: barretenberg, plonky2). Check the values passed with --define, --values-file or [template_values] in config".to_string())
+        );
+
+        // Remote templates are left to cargo-generate
+        assert_eq!(check_values(&TemplatePath::default(), &defines), Ok(()));
+    }
+
+    #[test]
+    fn test_may_prompt() {
+        let temp_dir = TempDir::new().unwrap();
+        let template_path = TemplatePath {
+            path: Some(temp_dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let mut defines = BTreeMap::from([("project_name".to_string(), "app".to_string())]);
+        assert!(!may_prompt(&template_path, &defines));
+
+        fs::write(
+            temp_dir.path().join("cargo-generate.toml"),
+            "[placeholders.backend]\ntype = \"string\"\nprompt = \"Backend?\"\n",
+        )
+        .unwrap();
+        assert!(may_prompt(&template_path, &defines));
+        defines.insert("backend".to_string(), "plonky2".to_string());
+        assert!(!may_prompt(&template_path, &defines));
+
+        fs::write(
+            temp_dir.path().join("cargo-generate.toml"),
+            "[hooks]\ninit = [\"init.rhai\"]\n",
+        )
+        .unwrap();
+        assert!(may_prompt(&template_path, &defines));
+        assert!(may_prompt(&TemplatePath::default(), &defines));
+    }
+
+    #[test]
+    fn test_render_template_requires_project_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = render_template(TemplatePath::default(), &BTreeMap::new(), temp_dir.path());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_render_local_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("template");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("README.md"), "# {{project_name}}\n").unwrap();
+
+        let destination = temp_dir.path().join("out");
+        fs::create_dir_all(&destination).unwrap();
+        let defines = BTreeMap::from([("project_name".to_string(), "my-app".to_string())]);
+        let template_path = TemplatePath {
+            path: Some(template_dir.display().to_string()),
+            ..Default::default()
+        };
+
+        let rendered = render_template(template_path, &defines, &destination).unwrap();
+        let tree = read_tree(&rendered).unwrap();
+        assert_eq!(tree[Path::new("README.md")], b"# my-app\n");
+        assert!(!rendered.join(".git").exists());
+    }
+}
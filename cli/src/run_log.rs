@@ -0,0 +1,216 @@
+//! Log file of a `cza new` run
+//!
+//! Every run writes a log under the XDG state directory, e.g.
+//! `~/.local/state/cza/logs/new-2025-01-01T12-00-00.000Z.log`. It records the resolved
+//! configuration, the arguments given to cargo-generate and the full stdout and stderr
+//! of every post-generation command, so a failure can be investigated or attached to a
+//! bug report. Only the [`MAX_LOGS`] most recent logs are kept. The `CZA_LOG_DIR`
+//! environment variable moves the logs to another directory.
+//!
+//! Logging never fails a run: when the file can't be created the [`RunLog`] is disabled
+//! and writes are ignored.
+
+use crate::config::Config;
+use anyhow::{Context, Result};
+use log::{debug, warn};
+use std::fmt::Debug;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Number of run logs kept in the logs directory
+pub const MAX_LOGS: usize = 20;
+
+/// Environment variable overriding the logs directory
+pub const LOG_DIR_ENV_VAR: &str = "CZA_LOG_DIR";
+
+/// Log file of one run
+#[derive(Debug)]
+pub struct RunLog {
+    file: Option<(PathBuf, File)>,
+}
+
+impl RunLog {
+    /// Start the log of a `command` run in the [`logs_dir`]
+    pub fn create(command: &str) -> Self {
+        match logs_dir().and_then(|dir| Self::create_in(&dir, command)) {
+            Ok(log) => log,
+            Err(e) => {
+                warn!("Could not create run log: {:#}", e);
+                Self::disabled()
+            }
+        }
+    }
+
+    /// Start the log of a `command` run in `dir`, removing the oldest logs
+    pub fn create_in(dir: &Path, command: &str) -> Result<Self> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create logs directory {}", dir.display()))?;
+        prune(dir, command, MAX_LOGS - 1);
+
+        // Colons aren't allowed in Windows file names
+        let timestamp = humantime::format_rfc3339_millis(SystemTime::now())
+            .to_string()
+            .replace(':', "-");
+        let path = dir.join(format!("{}-{}.log", command, timestamp));
+        let file = File::create(&path)
+            .with_context(|| format!("Failed to create run log {}", path.display()))?;
+        debug!("Logging this run to {}", path.display());
+
+        let log = Self {
+            file: Some((path, file)),
+        };
+        log.line(&format!(
+            "cza {} {}",
+            env!("CARGO_PKG_VERSION"),
+            std::env::args().skip(1).collect::<Vec<_>>().join(" ")
+        ));
+        if let Ok(dir) = std::env::current_dir() {
+            log.line(&format!("Working directory: {}", dir.display()));
+        }
+        Ok(log)
+    }
+
+    /// A log that writes nothing
+    pub fn disabled() -> Self {
+        Self { file: None }
+    }
+
+    /// Path of the log file, `None` when disabled
+    pub fn path(&self) -> Option<&Path> {
+        self.file.as_ref().map(|(path, _)| path.as_path())
+    }
+
+    /// Append a timestamped line
+    pub fn line(&self, text: &str) {
+        let timestamp = humantime::format_rfc3339_millis(SystemTime::now());
+        self.write(&format!("[{}] {}\n", timestamp, text));
+    }
+
+    /// Append a titled block, e.g. a debug representation or a TOML document
+    pub fn block(&self, title: &str, contents: &str) {
+        self.line(&format!("{}:", title));
+        for line in contents.trim_end().lines() {
+            self.write(&format!("    {}\n", line));
+        }
+    }
+
+    /// Append the resolved configuration
+    pub fn config(&self, config: &Config) {
+        match toml::to_string_pretty(config) {
+            Ok(contents) => self.block("Resolved configuration", &contents),
+            Err(e) => self.line(&format!("Could not serialize the configuration: {}", e)),
+        }
+    }
+
+    /// Append the debug representation of `value`
+    pub fn debug(&self, title: &str, value: &impl Debug) {
+        self.block(title, &format!("{:#?}", value));
+    }
+
+    /// Append a line printed by a subprocess
+    pub fn output(&self, line: &str) {
+        self.write(&format!("    | {}\n", line));
+    }
+
+    fn write(&self, text: &str) {
+        if let Some((path, file)) = &self.file {
+            // `&File` implements `Write`, so logging doesn't need `&mut self`
+            let mut file: &File = file;
+            if let Err(e) = file.write_all(text.as_bytes()) {
+                debug!("Could not write to run log {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Directory holding the run logs, `CZA_LOG_DIR` when set
+pub fn logs_dir() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os(LOG_DIR_ENV_VAR).filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    // Only Linux has a state directory, use the local data directory elsewhere
+    let state_dir = dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .context("Could not find state directory")?;
+    Ok(state_dir.join("cza").join("logs"))
+}
+
+/// Remove the oldest logs of `command` runs, keeping the `keep` most recent
+fn prune(dir: &Path, command: &str, keep: usize) {
+    let prefix = format!("{}-", command);
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut logs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|extension| extension == "log")
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(&prefix))
+        })
+        .collect();
+    // Timestamps in the names sort chronologically
+    logs.sort();
+    let excess = logs.len().saturating_sub(keep);
+    for path in logs.drain(..excess) {
+        debug!("Removing old run log {}", path.display());
+        let _ = fs::remove_file(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_run_log() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = RunLog::create_in(temp_dir.path(), "new").unwrap();
+        log.config(&Config::default());
+        log.debug("Arguments", &vec!["a", "b"]);
+        log.line("$ mise install");
+        log.output("installing noir");
+
+        let path = log.path().unwrap();
+        let name = path.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("new-") && name.ends_with(".log"));
+        assert!(!name.contains(':'));
+
+        let contents = fs::read_to_string(path).unwrap();
+        assert!(contents.contains(&format!("cza {}", env!("CARGO_PKG_VERSION"))));
+        assert!(contents.contains("Resolved configuration:\n"));
+        assert!(contents.contains("    [user]\n"));
+        assert!(contents.contains("Arguments:\n    [\n        \"a\",\n"));
+        assert!(contents.contains("] $ mise install\n    | installing noir\n"));
+    }
+
+    #[test]
+    fn test_disabled_run_log() {
+        let log = RunLog::disabled();
+        assert_eq!(log.path(), None);
+        log.line("ignored");
+    }
+
+    #[test]
+    fn test_prune() {
+        let temp_dir = TempDir::new().unwrap();
+        for index in 0..5 {
+            fs::write(temp_dir.path().join(format!("new-{}.log", index)), "").unwrap();
+        }
+        fs::write(temp_dir.path().join("other-0.log"), "").unwrap();
+
+        prune(temp_dir.path(), "new", 2);
+        let mut names: Vec<_> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["new-3.log", "new-4.log", "other-0.log"]);
+    }
+}
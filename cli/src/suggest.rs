@@ -0,0 +1,183 @@
+//! "Did you mean" suggestions for mistyped template names, configuration keys and profiles
+//!
+//! Lookups that fail return an [`UnknownName`] error carrying the closest known names,
+//! reported as one of the not-found [`CzaError`](crate::error::CzaError) variants.
+//! [`crate::output::format_error`] prints the names as a hint. With
+//! `development.autocorrect` enabled, commands use the only close candidate instead of
+//! failing.
+
+use std::fmt;
+
+/// Maximum number of suggestions reported
+const MAX_SUGGESTIONS: usize = 3;
+
+/// What kind of name wasn't found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameKind {
+    Template,
+    ConfigKey,
+    Profile,
+}
+
+/// A template or configuration key that doesn't exist, with similar known names
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownName {
+    pub kind: NameKind,
+    pub name: String,
+    /// Closest known names, best match first
+    pub suggestions: Vec<String>,
+}
+
+impl UnknownName {
+    /// Build the error for `name`, suggesting the closest of `candidates`
+    pub fn new<'a>(
+        kind: NameKind,
+        name: &str,
+        candidates: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            suggestions: similar_names(name, candidates),
+        }
+    }
+
+    /// The name to use instead, when exactly one candidate is close
+    pub fn autocorrection(&self) -> Option<&str> {
+        match self.suggestions.as_slice() {
+            [only] => Some(only),
+            _ => None,
+        }
+    }
+
+    /// The autocorrected name when `enabled` and there is a single candidate, else `self`
+    pub fn correct(self, enabled: bool) -> Result<String, Self> {
+        match self.autocorrection() {
+            Some(corrected) if enabled => Ok(corrected.to_string()),
+            _ => Err(self),
+        }
+    }
+
+    /// Error message without the suggestions
+    pub fn message(&self) -> String {
+        match self.kind {
+            NameKind::Template => format!("Template '{}' not found", self.name),
+            NameKind::ConfigKey => format!("Unknown configuration key: {}", self.name),
+            NameKind::Profile => format!("Profile '{}' not found", self.name),
+        }
+    }
+
+    /// "Did you mean" line, if there is anything to suggest
+    pub fn did_you_mean(&self) -> Option<String> {
+        did_you_mean(&self.suggestions)
+    }
+
+    /// Where to look up valid names
+    pub fn hint(&self) -> &'static str {
+        match self.kind {
+            NameKind::Template => "Use 'cza list' to see available templates.",
+            NameKind::ConfigKey => "Use 'cza config list' to see available keys.",
+            NameKind::Profile => "Use 'cza config profile list' to see available profiles.",
+        }
+    }
+}
+
+impl fmt::Display for UnknownName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.", self.message())?;
+        match self.did_you_mean() {
+            Some(did_you_mean) => write!(f, " {}", did_you_mean),
+            None => write!(f, " {}", self.hint()),
+        }
+    }
+}
+
+impl std::error::Error for UnknownName {}
+
+/// "Did you mean" line for `suggestions`, if there are any
+pub fn did_you_mean(suggestions: &[String]) -> Option<String> {
+    let quoted: Vec<String> = suggestions
+        .iter()
+        .map(|name| format!("'{}'", name))
+        .collect();
+    match quoted.as_slice() {
+        [] => None,
+        [only] => Some(format!("Did you mean {}?", only)),
+        [rest @ .., last] => Some(format!("Did you mean {} or {}?", rest.join(", "), last)),
+    }
+}
+
+/// Names from `candidates` within a small edit distance of `name`, closest first
+///
+/// Up to a third of the characters may differ (at least one), comparing
+/// case-insensitively and counting swapped adjacent characters as a single edit.
+pub fn similar_names<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let name = name.to_lowercase();
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    let mut close: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .map(|candidate| {
+            let distance = strsim::damerau_levenshtein(&name, &candidate.to_lowercase());
+            (distance, candidate)
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    close.sort();
+    close.dedup();
+    close
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATES: &[&str] = &["cairo-vite", "noir-vite", "acme/noir-vite"];
+
+    #[test]
+    fn test_similar_names() {
+        assert_eq!(
+            similar_names("noir-vit", TEMPLATES.iter().copied()),
+            vec!["noir-vite".to_string()]
+        );
+        assert_eq!(
+            similar_names("NOIR-VTIE", TEMPLATES.iter().copied()),
+            vec!["noir-vite".to_string()]
+        );
+        assert!(similar_names("starknet", TEMPLATES.iter().copied()).is_empty());
+        assert_eq!(
+            similar_names("user.autor", ["user.author", "user.email"]),
+            vec!["user.author".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_unknown_name() {
+        let err = UnknownName::new(NameKind::Template, "noir-vit", TEMPLATES.iter().copied());
+        assert_eq!(err.autocorrection(), Some("noir-vite"));
+        assert!(err.clone().correct(false).is_err());
+        assert_eq!(err.clone().correct(true).unwrap(), "noir-vite");
+        assert_eq!(
+            err.to_string(),
+            "Template 'noir-vit' not found. Did you mean 'noir-vite'?"
+        );
+
+        let err = UnknownName::new(NameKind::ConfigKey, "user.x", ["user.a", "user.b"]);
+        assert_eq!(err.autocorrection(), None);
+        assert_eq!(
+            err.did_you_mean().as_deref(),
+            Some("Did you mean 'user.a' or 'user.b'?")
+        );
+
+        let err = UnknownName::new(NameKind::ConfigKey, "nothing.close", ["user.author"]);
+        assert_eq!(err.did_you_mean(), None);
+        assert_eq!(
+            err.to_string(),
+            "Unknown configuration key: nothing.close. Use 'cza config list' to see available keys."
+        );
+    }
+}
@@ -4,8 +4,14 @@
 //! It provides functionality to:
 //!
 //! - Load available templates from the embedded registry
+//! - Merge templates from external registries, namespaced per registry
+//! - Merge user-defined templates from the configuration file
+//! - Resolve local filesystem templates (`file://` entries and `cza new --path`)
+//! - Resolve the commit a template revision points to
+//! - Collect template values from `--define`, values files and config ([`values`])
 //! - Validate template configuration
-//! - Check system prerequisites (git availability)
+//! - Check system prerequisites (git availability) and per-template tool requirements
+//!   ([`prereqs`])
 //! - Support template pinning to specific git revisions
 //!
 //! ## Template Structure
@@ -16,6 +22,30 @@
 //! - Subfolder path within the repository
 //! - Associated ZK frameworks
 //! - Optional git revision (commit SHA, tag, or branch) for pinning
+//! - Optional `requires` table of tools and version requirements
+//! - Optional `min_cza_version`/`max_cza_version` bounds ([`compat`])
+//!
+//! ## User Templates
+//!
+//! Additional templates can be declared as `[templates.<key>]` tables in the user
+//! configuration file using the same fields as `templates.toml`. User entries take
+//! precedence over built-in entries with the same key. Every [`TemplateInfo`] carries
+//! a [`TemplateSource`] marker telling where it came from.
+//!
+//! ## External Registries
+//!
+//! Extra registries listed under `[registry.sources]` in the config or passed with
+//! `--registry` are loaded by [`remote`] and their templates are namespaced with the
+//! registry name (e.g. `acme/noir-vite`), so keys from different registries can't
+//! collide. Registries that can't be loaded are skipped with a warning, leaving the
+//! embedded templates available offline.
+//!
+//! ## Local Templates
+//!
+//! A template whose `repository` starts with `file://` is read from the local filesystem
+//! instead of being cloned, which allows iterating on templates fully offline. When a
+//! revision is requested for a local template, the enclosing git checkout is used and
+//! the revision is checked out from it.
 //!
 //! ## Template Pinning
 //!
@@ -40,21 +70,125 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
-use anyhow::{anyhow, Result};
-use log::debug;
+use crate::config::Config;
+use crate::error::CzaError;
+use crate::suggest::{NameKind, UnknownName};
+use anyhow::{anyhow, Context, Result};
+use cargo_generate::TemplatePath;
+use log::{debug, warn};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+pub mod compat;
+pub mod details;
+pub mod prereqs;
+mod remote;
+mod values;
+
+pub use compat::VersionBounds;
+pub use remote::{registry_cache_dir, RegistrySpec};
+pub use values::{parse_define, read_values_file, table_values, TemplateValues};
+
+/// URL scheme prefix marking templates stored on the local filesystem
+pub const FILE_SCHEME: &str = "file://";
+
 /// Template registry containing all available templates
 #[derive(Deserialize)]
 pub struct TemplateRegistry {
+    /// Optional registry name, used as the default namespace for external registries
+    #[serde(default)]
+    pub name: Option<String>,
     /// Map of template keys to template information
     pub templates: HashMap<String, TemplateInfo>,
 }
 
+impl TemplateRegistry {
+    /// Merge templates into the registry, overriding entries with the same key
+    pub fn merge(&mut self, templates: &BTreeMap<String, TemplateInfo>, source: TemplateSource) {
+        for (key, info) in templates {
+            if self.templates.contains_key(key) {
+                debug!(
+                    "Template '{}' from {} overrides existing entry",
+                    key, source
+                );
+            }
+            let mut info = info.clone();
+            info.source = source;
+            self.templates.insert(key.clone(), info);
+        }
+    }
+
+    /// Look up a template by key
+    ///
+    /// Unknown keys fail with [`CzaError::TemplateNotFound`], suggesting similar keys.
+    /// With `autocorrect`, the only close key is used instead; the returned key tells which.
+    pub fn find(&self, key: &str, autocorrect: bool) -> Result<(&String, &TemplateInfo), CzaError> {
+        if let Some(found) = self.templates.get_key_value(key) {
+            return Ok(found);
+        }
+        let unknown = UnknownName::new(
+            NameKind::Template,
+            key,
+            self.templates.keys().map(String::as_str),
+        );
+        unknown
+            .autocorrection()
+            .filter(|_| autocorrect)
+            .and_then(|corrected| self.templates.get_key_value(corrected))
+            .ok_or_else(|| unknown.into())
+    }
+
+    /// Merge an external registry, prefixing its template keys with `namespace/`
+    ///
+    /// Fails when another registry already uses the namespace, so namespaced templates
+    /// never override each other.
+    pub fn merge_namespaced(&mut self, namespace: &str, registry: TemplateRegistry) -> Result<()> {
+        let prefix = format!("{}/", namespace);
+        if self.templates.keys().any(|key| key.starts_with(&prefix)) {
+            return Err(anyhow!(
+                "Namespace '{}' is already used by another registry, give this one a different name with NAME=LOCATION",
+                namespace
+            ));
+        }
+        for (key, mut info) in registry.templates {
+            info.source = TemplateSource::Registry;
+            self.templates.insert(format!("{}{}", prefix, key), info);
+        }
+        Ok(())
+    }
+}
+
+/// Where a template entry was defined, in increasing order of precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateSource {
+    /// Embedded `templates.toml` shipped with cza
+    #[default]
+    Builtin,
+    /// External registry from config or `--registry`
+    Registry,
+    /// `[templates.<key>]` tables in the user configuration file
+    User,
+    /// Local directory passed with `cza new --path`
+    Local,
+}
+
+impl fmt::Display for TemplateSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateSource::Builtin => write!(f, "built-in"),
+            TemplateSource::Registry => write!(f, "external registry"),
+            TemplateSource::User => write!(f, "user config"),
+            TemplateSource::Local => write!(f, "local path"),
+        }
+    }
+}
+
 /// Information about a specific template
-#[derive(Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TemplateInfo {
     /// Display name of the template
     pub name: String,
@@ -69,6 +203,188 @@ pub struct TemplateInfo {
     /// Optional git revision (commit SHA, tag, or branch) to pin the template
     #[serde(default)]
     pub revision: Option<String>,
+    /// Tools the template needs, mapped to semver requirements (`*` for any version)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub requires: BTreeMap<String, String>,
+    /// Range of cza versions the template works with (`min_cza_version`/`max_cza_version`)
+    #[serde(flatten)]
+    pub compatibility: VersionBounds,
+    /// Where this template entry was defined (not read from or written to TOML)
+    #[serde(skip)]
+    pub source: TemplateSource,
+}
+
+impl TemplateInfo {
+    /// Local directory backing this template, if its repository is a `file://` URL
+    pub fn local_path(&self) -> Option<PathBuf> {
+        self.repository.strip_prefix(FILE_SCHEME).map(PathBuf::from)
+    }
+
+    /// Whether this template is read from the local filesystem
+    pub fn is_local(&self) -> bool {
+        self.repository.starts_with(FILE_SCHEME)
+    }
+
+    /// Build the cargo-generate template path for this template
+    ///
+    /// Remote templates are cloned from their git repository. Local templates are copied
+    /// from disk, unless a revision is requested, in which case the enclosing git
+    /// checkout is cloned at that revision.
+    pub fn template_path(&self, revision: Option<String>) -> Result<TemplatePath> {
+        let Some(base) = self.local_path() else {
+            return Ok(TemplatePath {
+                git: Some(self.repository.clone()),
+                subfolder: Some(self.subfolder.clone()),
+                revision,
+                ..Default::default()
+            });
+        };
+
+        let dir = if self.subfolder.is_empty() {
+            base
+        } else {
+            base.join(&self.subfolder)
+        };
+        let dir = dir
+            .canonicalize()
+            .with_context(|| format!("Template directory '{}' not found", dir.display()))?;
+
+        match revision {
+            Some(revision) => {
+                let root = find_git_root(&dir).ok_or_else(|| {
+                    anyhow!(
+                        "Cannot pin revision '{}': '{}' is not inside a git checkout",
+                        revision,
+                        dir.display()
+                    )
+                })?;
+                let subfolder = dir
+                    .strip_prefix(&root)
+                    .ok()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .filter(|p| !p.is_empty());
+                Ok(TemplatePath {
+                    git: Some(root.to_string_lossy().to_string()),
+                    subfolder,
+                    revision: Some(revision),
+                    ..Default::default()
+                })
+            }
+            None => Ok(TemplatePath {
+                path: Some(dir.to_string_lossy().to_string()),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+/// Build a template entry for a local template directory (`cza new --path`)
+///
+/// Returns the template key (the directory name) alongside the template information.
+pub fn local_template(path: &Path) -> Result<(String, TemplateInfo)> {
+    let dir = path
+        .canonicalize()
+        .with_context(|| format!("Template directory '{}' not found", path.display()))?;
+    let key = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "local".to_string());
+
+    let info = TemplateInfo {
+        name: key.clone(),
+        description: format!("Local template at {}", dir.display()),
+        repository: format!("{}{}", FILE_SCHEME, dir.display()),
+        subfolder: String::new(),
+        frameworks: Vec::new(),
+        revision: None,
+        requires: Default::default(),
+        compatibility: Default::default(),
+        source: TemplateSource::Local,
+    };
+    Ok((key, info))
+}
+
+/// Resolve the commit SHA a template revision points to
+///
+/// Remote templates are resolved with `git ls-remote` (`HEAD` when no revision is given),
+/// or from the template cache with `--offline`, local templates with `git rev-parse` in
+/// their enclosing checkout. Returns `None` when the commit can't be determined, e.g.
+/// offline or for abbreviated SHAs.
+pub fn resolve_commit(template_info: &TemplateInfo, revision: Option<&str>) -> Option<String> {
+    if let Some(rev) = revision {
+        if is_full_sha(rev) {
+            return Some(rev.to_string());
+        }
+    }
+
+    let commit = match template_info.local_path() {
+        Some(base) => {
+            let root = find_git_root(&base.join(&template_info.subfolder))?;
+            let rev = format!("{}^{{commit}}", revision.unwrap_or("HEAD"));
+            git_output(&["-C", &root.to_string_lossy(), "rev-parse", "--verify", &rev])
+        }
+        None if crate::context::offline() => crate::cache::TemplateCache::open()
+            .ok()
+            .and_then(|cache| cache.resolve(&template_info.repository, revision)),
+        None => {
+            let rev = revision.unwrap_or("HEAD");
+            git_output(&["ls-remote", &template_info.repository, rev])
+                .and_then(|refs| parse_ls_remote(&refs, rev))
+        }
+    };
+
+    debug!(
+        "Resolved revision {} of {} to commit {:?}",
+        revision.unwrap_or("HEAD"),
+        template_info.repository,
+        commit
+    );
+    commit.filter(|sha| is_full_sha(sha))
+}
+
+/// Pick the commit of `rev` from `git ls-remote` output
+///
+/// `ls-remote` also lists refs that merely end with `rev`, e.g. `refs/tags/foo/v1` for
+/// `v1`, so only exact names count: the peeled tag (`^{}`), the tag, the branch, then
+/// `rev` itself (`HEAD` or a full ref name).
+fn parse_ls_remote(output: &str, rev: &str) -> Option<String> {
+    let refs: Vec<(&str, &str)> = output
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .collect();
+    [
+        format!("refs/tags/{}^{{}}", rev),
+        format!("refs/tags/{}", rev),
+        format!("refs/heads/{}", rev),
+        rev.to_string(),
+    ]
+    .iter()
+    .find_map(|wanted| refs.iter().find(|(_, name)| name == wanted))
+    .map(|(sha, _)| sha.to_string())
+}
+
+/// Whether a revision is a full 40-character hex commit SHA
+pub fn is_full_sha(rev: &str) -> bool {
+    rev.len() == 40 && rev.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Run git and return its trimmed stdout on success
+fn git_output(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Find the root of the git checkout containing `dir`
+fn find_git_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|ancestor| ancestor.join(".git").exists())
+        .map(Path::to_path_buf)
 }
 
 /// Load the embedded template registry from templates.toml
@@ -77,6 +393,41 @@ pub fn load_template_registry() -> Result<TemplateRegistry> {
     toml::from_str(templates_toml).map_err(|e| anyhow!("Failed to parse template registry: {}", e))
 }
 
+/// Load the embedded template registry merged with external registries and
+/// user-defined templates from config
+///
+/// External registries come from `[registry.sources]` in config followed by `extra`
+/// (the `--registry` flags). User templates take precedence over everything else.
+pub fn load_registry(config: &Config, extra: &[RegistrySpec]) -> Result<TemplateRegistry> {
+    let mut registry = load_template_registry()?;
+
+    let configured = config
+        .registry
+        .sources
+        .iter()
+        .map(|(name, location)| RegistrySpec {
+            name: Some(name.clone()),
+            location: location.clone(),
+        });
+    for spec in configured.chain(extra.iter().cloned()) {
+        let merged = remote::load_registry_spec(&spec, config.registry.cache_ttl)
+            .and_then(|(namespace, external)| registry.merge_namespaced(&namespace, external));
+        if let Err(e) = merged {
+            warn!(
+                "Skipping registry {}: {:#}. Built-in templates are still available.",
+                spec.location, e
+            );
+        }
+    }
+
+    debug!(
+        "Merging {} user-defined templates from config",
+        config.templates.len()
+    );
+    registry.merge(&config.templates, TemplateSource::User);
+    Ok(registry)
+}
+
 /// Validate that a template's repository and subfolder exist
 pub fn validate_template(template_info: &TemplateInfo) -> Result<()> {
     debug!(
@@ -92,6 +443,22 @@ pub fn validate_template(template_info: &TemplateInfo) -> Result<()> {
         return Err(anyhow!("Template repository URL cannot be empty"));
     }
 
+    // Local templates only need to point at an existing directory
+    if let Some(base) = template_info.local_path() {
+        let dir = base.join(&template_info.subfolder);
+        if !dir.is_dir() {
+            return Err(anyhow!(
+                "Template directory '{}' does not exist",
+                dir.display()
+            ));
+        }
+        debug!(
+            "Local template validation passed for {}",
+            template_info.name
+        );
+        return Ok(());
+    }
+
     if template_info.subfolder.is_empty() {
         return Err(anyhow!("Template subfolder cannot be empty"));
     }
@@ -167,6 +534,9 @@ mod tests {
             subfolder: "test-template".to_string(),
             frameworks: vec!["test".to_string()],
             revision: None,
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source: TemplateSource::Builtin,
         };
 
         assert!(validate_template(&valid_template).is_ok());
@@ -181,6 +551,9 @@ mod tests {
             subfolder: "test-template".to_string(),
             frameworks: vec!["test".to_string()],
             revision: None,
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source: TemplateSource::Builtin,
         };
 
         let result = validate_template(&invalid_template);
@@ -200,6 +573,9 @@ mod tests {
             subfolder: "".to_string(),
             frameworks: vec!["test".to_string()],
             revision: None,
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source: TemplateSource::Builtin,
         };
 
         let result = validate_template(&invalid_template);
@@ -219,6 +595,9 @@ mod tests {
             subfolder: "test-template".to_string(),
             frameworks: vec!["test".to_string()],
             revision: None,
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source: TemplateSource::Builtin,
         };
 
         let result = validate_template(&invalid_template);
@@ -238,6 +617,9 @@ mod tests {
             subfolder: "template".to_string(),
             frameworks: vec!["test".to_string()],
             revision: None,
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source: TemplateSource::Builtin,
         };
 
         assert!(validate_template(&github_template).is_ok());
@@ -252,6 +634,9 @@ mod tests {
             subfolder: "template".to_string(),
             frameworks: vec!["test".to_string()],
             revision: None,
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source: TemplateSource::Builtin,
         };
 
         assert!(validate_template(&ssh_template).is_ok());
@@ -276,6 +661,9 @@ mod tests {
             subfolder: "template".to_string(),
             frameworks: vec!["test".to_string()],
             revision: Some("abc123def".to_string()),
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source: TemplateSource::Builtin,
         };
 
         assert_eq!(
@@ -303,4 +691,341 @@ revision = "abc123def"
         let template = &registry.templates["pinned-template"];
         assert_eq!(template.revision, Some("abc123def".to_string()));
     }
+
+    #[test]
+    fn test_embedded_templates_are_builtin() {
+        let registry = load_template_registry().unwrap();
+        assert!(registry
+            .templates
+            .values()
+            .all(|t| t.source == TemplateSource::Builtin));
+    }
+
+    #[test]
+    fn test_load_registry_merges_user_templates() {
+        let mut config = Config::default();
+        config.templates.insert(
+            "my-starter".to_string(),
+            TemplateInfo {
+                name: "My Starter".to_string(),
+                description: "A custom starter".to_string(),
+                repository: "https://github.com/me/starters".to_string(),
+                subfolder: "my-starter".to_string(),
+                frameworks: vec!["noir".to_string()],
+                revision: None,
+                requires: Default::default(),
+                compatibility: Default::default(),
+                source: TemplateSource::Builtin,
+            },
+        );
+
+        let registry = load_registry(&config, &[]).unwrap();
+        assert!(registry.templates.contains_key("noir-vite"));
+        assert_eq!(
+            registry.templates["noir-vite"].source,
+            TemplateSource::Builtin
+        );
+        assert_eq!(
+            registry.templates["my-starter"].source,
+            TemplateSource::User
+        );
+    }
+
+    #[test]
+    fn test_user_template_overrides_builtin() {
+        let toml_content = r#"
+[templates.noir-vite]
+name = "My Noir Fork"
+description = "Forked noir template"
+repository = "https://github.com/me/cza-templates"
+subfolder = "noir-vite"
+frameworks = ["noir"]
+"#;
+        let config: Config = toml::from_str(toml_content).unwrap();
+
+        let registry = load_registry(&config, &[]).unwrap();
+        let template = &registry.templates["noir-vite"];
+        assert_eq!(template.name, "My Noir Fork");
+        assert_eq!(template.repository, "https://github.com/me/cza-templates");
+        assert_eq!(template.source, TemplateSource::User);
+        assert!(registry.templates.contains_key("cairo-vite"));
+    }
+
+    fn local_template_info(dir: &Path, subfolder: &str) -> TemplateInfo {
+        TemplateInfo {
+            name: "Local Template".to_string(),
+            description: "A local template".to_string(),
+            repository: format!("{}{}", FILE_SCHEME, dir.display()),
+            subfolder: subfolder.to_string(),
+            frameworks: vec!["test".to_string()],
+            revision: None,
+            requires: Default::default(),
+            compatibility: Default::default(),
+            source: TemplateSource::User,
+        }
+    }
+
+    #[test]
+    fn test_validate_template_file_url() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("my-template")).unwrap();
+
+        let template = local_template_info(temp_dir.path(), "my-template");
+        assert!(template.is_local());
+        assert!(validate_template(&template).is_ok());
+
+        // Local templates may point directly at the template directory
+        let template = local_template_info(temp_dir.path(), "");
+        assert!(validate_template(&template).is_ok());
+    }
+
+    #[test]
+    fn test_validate_template_file_url_missing_dir() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template = local_template_info(temp_dir.path(), "missing");
+
+        let result = validate_template(&template);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn test_template_path_remote() {
+        let registry = load_template_registry().unwrap();
+        let template = &registry.templates["noir-vite"];
+        assert!(!template.is_local());
+
+        let path = template.template_path(Some("v1".to_string())).unwrap();
+        assert_eq!(path.git, Some(template.repository.clone()));
+        assert_eq!(path.subfolder, Some("noir-vite".to_string()));
+        assert_eq!(path.revision, Some("v1".to_string()));
+        assert_eq!(path.path, None);
+    }
+
+    #[test]
+    fn test_template_path_local_directory() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("my-template")).unwrap();
+        let template = local_template_info(temp_dir.path(), "my-template");
+
+        let path = template.template_path(None).unwrap();
+        assert_eq!(path.git, None);
+        assert!(path.path.unwrap().ends_with("my-template"));
+    }
+
+    #[test]
+    fn test_template_path_local_revision_uses_git_root() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join(".git")).unwrap();
+        std::fs::create_dir(temp_dir.path().join("my-template")).unwrap();
+        let template = local_template_info(temp_dir.path(), "my-template");
+
+        let path = template.template_path(Some("main".to_string())).unwrap();
+        assert_eq!(
+            path.git,
+            Some(
+                temp_dir
+                    .path()
+                    .canonicalize()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            )
+        );
+        assert_eq!(path.subfolder, Some("my-template".to_string()));
+        assert_eq!(path.revision, Some("main".to_string()));
+    }
+
+    #[test]
+    fn test_template_path_local_revision_without_git() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template = local_template_info(temp_dir.path(), "");
+
+        let result = template.template_path(Some("main".to_string()));
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("not inside a git checkout"));
+    }
+
+    #[test]
+    fn test_local_template() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path().join("my-template");
+        std::fs::create_dir(&dir).unwrap();
+
+        let (key, info) = local_template(&dir).unwrap();
+        assert_eq!(key, "my-template");
+        assert_eq!(info.source, TemplateSource::Local);
+        assert!(info.is_local());
+        assert!(validate_template(&info).is_ok());
+
+        assert!(local_template(&temp_dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_load_registry_namespaces_external_registries() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("acme.toml");
+        std::fs::write(
+            &path,
+            r#"
+[templates.noir-vite]
+name = "Acme Noir"
+description = "Acme's Noir starter"
+repository = "https://github.com/acme/starters"
+subfolder = "noir-vite"
+frameworks = ["noir"]
+"#,
+        )
+        .unwrap();
+
+        let spec: RegistrySpec = path.display().to_string().parse().unwrap();
+        let registry = load_registry(&Config::default(), &[spec]).unwrap();
+
+        // Both the built-in and the namespaced template are available
+        assert_eq!(
+            registry.templates["noir-vite"].name,
+            "Noir + Vite + TanStack"
+        );
+        assert_eq!(registry.templates["acme/noir-vite"].name, "Acme Noir");
+        assert_eq!(
+            registry.templates["acme/noir-vite"].source,
+            TemplateSource::Registry
+        );
+    }
+
+    #[test]
+    fn test_load_registry_rejects_namespace_collisions() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut specs = Vec::new();
+        for (file, template_name) in [("first.toml", "First"), ("second.toml", "Second")] {
+            let path = temp_dir.path().join(file);
+            std::fs::write(
+                &path,
+                format!(
+                    r#"
+name = "acme"
+
+[templates.noir-vite]
+name = "{}"
+description = "Acme's Noir starter"
+repository = "https://github.com/acme/starters"
+subfolder = "noir-vite"
+frameworks = ["noir"]
+"#,
+                    template_name
+                ),
+            )
+            .unwrap();
+            specs.push(path.display().to_string().parse().unwrap());
+        }
+
+        // The second registry is skipped instead of replacing the first one's templates
+        let registry = load_registry(&Config::default(), &specs).unwrap();
+        assert_eq!(registry.templates["acme/noir-vite"].name, "First");
+    }
+
+    #[test]
+    fn test_load_registry_skips_unavailable_registries() {
+        let spec: RegistrySpec = "missing=/nonexistent/registry.toml".parse().unwrap();
+        let registry = load_registry(&Config::default(), &[spec]).unwrap();
+
+        assert!(registry.templates.contains_key("noir-vite"));
+        assert!(!registry
+            .templates
+            .keys()
+            .any(|key| key.starts_with("missing/")));
+    }
+
+    #[test]
+    fn test_parse_ls_remote_prefers_peeled_tag() {
+        let output = "1111111111111111111111111111111111111111\trefs/tags/v1\n\
+                      2222222222222222222222222222222222222222\trefs/tags/v1^{}\n";
+        assert_eq!(
+            parse_ls_remote(output, "v1"),
+            Some("2222222222222222222222222222222222222222".to_string())
+        );
+
+        let output = "3333333333333333333333333333333333333333\tHEAD\n";
+        assert_eq!(
+            parse_ls_remote(output, "HEAD"),
+            Some("3333333333333333333333333333333333333333".to_string())
+        );
+        assert_eq!(parse_ls_remote("", "HEAD"), None);
+    }
+
+    #[test]
+    fn test_parse_ls_remote_matches_exact_ref() {
+        // Peeled tags of other refs ending with the revision are ignored
+        let output = "1111111111111111111111111111111111111111\trefs/heads/v1\n\
+                      2222222222222222222222222222222222222222\trefs/tags/other/v1\n\
+                      3333333333333333333333333333333333333333\trefs/tags/other/v1^{}\n";
+        assert_eq!(
+            parse_ls_remote(output, "v1"),
+            Some("1111111111111111111111111111111111111111".to_string())
+        );
+
+        // A tag wins over a branch of the same name
+        let output = "1111111111111111111111111111111111111111\trefs/heads/v1\n\
+                      2222222222222222222222222222222222222222\trefs/tags/v1\n";
+        assert_eq!(
+            parse_ls_remote(output, "v1"),
+            Some("2222222222222222222222222222222222222222".to_string())
+        );
+
+        let output = "1111111111111111111111111111111111111111\trefs/heads/release/main\n";
+        assert_eq!(parse_ls_remote(output, "main"), None);
+    }
+
+    #[test]
+    fn test_resolve_commit_full_sha() {
+        let registry = load_template_registry().unwrap();
+        let template = &registry.templates["noir-vite"];
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+
+        assert_eq!(resolve_commit(template, Some(sha)), Some(sha.to_string()));
+    }
+
+    #[test]
+    fn test_resolve_commit_local_git_checkout() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .map(|output| output.status.success())
+                .unwrap_or(false)
+        };
+        // Skip when git isn't available in the test environment
+        if !git(&["init", "-q"]) {
+            return;
+        }
+        std::fs::write(dir.join("README.md"), "template").unwrap();
+        git(&["add", "."]);
+        git(&[
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-qm",
+            "init",
+        ]);
+
+        let template = local_template_info(dir, "");
+        let commit = resolve_commit(&template, None).unwrap();
+        assert!(is_full_sha(&commit));
+        assert_eq!(resolve_commit(&template, Some("HEAD")), Some(commit));
+    }
+
+    #[test]
+    fn test_resolve_commit_local_without_git() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template = local_template_info(temp_dir.path(), "");
+        assert_eq!(resolve_commit(&template, None), None);
+    }
 }
@@ -0,0 +1,228 @@
+//! cza version compatibility
+//!
+//! Templates can declare the range of cza versions they work with, either in their
+//! registry entry or in a `cza.toml` manifest at the root of the template repository:
+//!
+//! ```toml
+//! # Applies to every template in the repository
+//! min_cza_version = "2.4.0"
+//!
+//! # Overrides for the template in the noir-vite subfolder
+//! [templates.noir-vite]
+//! min_cza_version = "2.5.0"
+//! max_cza_version = "3.0.0"
+//! ```
+//!
+//! Both bounds are inclusive. Partial versions such as `2.5` are padded with zeros.
+
+use super::prereqs;
+use anyhow::{anyhow, Context, Result};
+use self_update::cargo_crate_version;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Name of the manifest file at the root of a template repository
+pub const MANIFEST_FILE_NAME: &str = "cza.toml";
+
+/// Range of cza versions a template supports
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct VersionBounds {
+    /// Oldest cza version the template works with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_cza_version: Option<String>,
+    /// Newest cza version the template works with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cza_version: Option<String>,
+}
+
+impl VersionBounds {
+    /// Compare the bounds with the running cza version
+    pub fn check(&self) -> Result<Option<Incompatibility>> {
+        self.check_version(current_version())
+    }
+
+    /// Compare the bounds with `version`
+    pub fn check_version(&self, version: &str) -> Result<Option<Incompatibility>> {
+        let current = parse(version, "cza version")?;
+        if let Some(ref min) = self.min_cza_version {
+            if current < parse(min, "min_cza_version")? {
+                return Ok(Some(Incompatibility::TooOld { min: min.clone() }));
+            }
+        }
+        if let Some(ref max) = self.max_cza_version {
+            if current > parse(max, "max_cza_version")? {
+                return Ok(Some(Incompatibility::TooNew { max: max.clone() }));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Why the running cza version can't use a template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Incompatibility {
+    /// The template needs a newer cza
+    TooOld { min: String },
+    /// The template only supports older cza versions
+    TooNew { max: String },
+}
+
+impl Incompatibility {
+    /// What the user can do about it
+    pub fn hint(&self) -> &'static str {
+        match self {
+            Incompatibility::TooOld { .. } => "Run 'cza update' to install the latest version",
+            Incompatibility::TooNew { .. } => {
+                "Use an older template revision with --revision, or an older cza release"
+            }
+        }
+    }
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Incompatibility::TooOld { min } => write!(f, "requires cza >= {}", min),
+            Incompatibility::TooNew { max } => write!(f, "supports cza up to {}", max),
+        }
+    }
+}
+
+/// `cza.toml` manifest of a template repository
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TemplateManifest {
+    #[serde(flatten)]
+    pub bounds: VersionBounds,
+    /// Per-template overrides keyed by subfolder
+    #[serde(default)]
+    pub templates: BTreeMap<String, VersionBounds>,
+}
+
+impl TemplateManifest {
+    /// Read the manifest at the root of `repository_dir`, if there is one
+    pub fn read(repository_dir: &Path) -> Result<Option<Self>> {
+        let path = repository_dir.join(MANIFEST_FILE_NAME);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let manifest = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(manifest))
+    }
+
+    /// Bounds for the template in `subfolder`, falling back to the repository-wide ones
+    pub fn bounds_for(&self, subfolder: &str) -> VersionBounds {
+        let specific = self.templates.get(subfolder).cloned().unwrap_or_default();
+        VersionBounds {
+            min_cza_version: specific
+                .min_cza_version
+                .or_else(|| self.bounds.min_cza_version.clone()),
+            max_cza_version: specific
+                .max_cza_version
+                .or_else(|| self.bounds.max_cza_version.clone()),
+        }
+    }
+}
+
+/// Version of the running cza binary
+pub fn current_version() -> &'static str {
+    cargo_crate_version!()
+}
+
+fn parse(version: &str, what: &str) -> Result<semver::Version> {
+    prereqs::parse_lenient(version).ok_or_else(|| anyhow!("Invalid {} '{}'", what, version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn bounds(min: Option<&str>, max: Option<&str>) -> VersionBounds {
+        VersionBounds {
+            min_cza_version: min.map(str::to_string),
+            max_cza_version: max.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_check_version() {
+        assert_eq!(bounds(None, None).check_version("2.4.0").unwrap(), None);
+        assert_eq!(
+            bounds(Some("2.4"), Some("2.4.0"))
+                .check_version("2.4.0")
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            bounds(Some("2.5"), None).check_version("2.4.0").unwrap(),
+            Some(Incompatibility::TooOld {
+                min: "2.5".to_string()
+            })
+        );
+        assert_eq!(
+            bounds(None, Some("2.3.9")).check_version("2.4.0").unwrap(),
+            Some(Incompatibility::TooNew {
+                max: "2.3.9".to_string()
+            })
+        );
+        assert!(bounds(Some("latest"), None).check_version("2.4.0").is_err());
+    }
+
+    #[test]
+    fn test_current_version_is_compatible_without_bounds() {
+        assert_eq!(VersionBounds::default().check().unwrap(), None);
+    }
+
+    #[test]
+    fn test_incompatibility_display() {
+        let too_old = Incompatibility::TooOld {
+            min: "3.0.0".to_string(),
+        };
+        assert_eq!(too_old.to_string(), "requires cza >= 3.0.0");
+        assert!(too_old.hint().contains("cza update"));
+        let too_new = Incompatibility::TooNew {
+            max: "1.0.0".to_string(),
+        };
+        assert_eq!(too_new.to_string(), "supports cza up to 1.0.0");
+    }
+
+    #[test]
+    fn test_read_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(TemplateManifest::read(temp_dir.path()).unwrap().is_none());
+
+        fs::write(
+            temp_dir.path().join(MANIFEST_FILE_NAME),
+            r#"
+min_cza_version = "2.0.0"
+max_cza_version = "3.0.0"
+
+[templates.noir-vite]
+min_cza_version = "2.5.0"
+"#,
+        )
+        .unwrap();
+        let manifest = TemplateManifest::read(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(
+            manifest.bounds_for("noir-vite"),
+            bounds(Some("2.5.0"), Some("3.0.0"))
+        );
+        assert_eq!(
+            manifest.bounds_for("cairo-vite"),
+            bounds(Some("2.0.0"), Some("3.0.0"))
+        );
+
+        fs::write(
+            temp_dir.path().join(MANIFEST_FILE_NAME),
+            "min_cza_version = [",
+        )
+        .unwrap();
+        assert!(TemplateManifest::read(temp_dir.path()).is_err());
+    }
+}
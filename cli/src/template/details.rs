@@ -0,0 +1,267 @@
+//! Template contents shown by `cza info`
+//!
+//! Reads the README of a template directory and the placeholders and hooks declared in
+//! its `cargo-generate.toml`:
+//!
+//! ```toml
+//! [placeholders.backend]
+//! type = "string"
+//! prompt = "Which proving backend?"
+//! choices = ["barretenberg", "plonky2"]
+//! default = "barretenberg"
+//!
+//! [hooks]
+//! post = ["setup.rhai"]
+//! ```
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Name of the cargo-generate configuration file of a template
+pub const GENERATE_FILE_NAME: &str = "cargo-generate.toml";
+
+/// A value `cza new` asks for, set with `--define NAME=VALUE`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Placeholder {
+    pub name: String,
+    /// Value type (`string` or `bool`)
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Regular expression string values must match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+}
+
+/// Rhai scripts cargo-generate runs while rendering the template
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Hooks {
+    /// Run before placeholders are prompted for
+    #[serde(default)]
+    pub init: Vec<String>,
+    /// Run before the files are rendered
+    #[serde(default)]
+    pub pre: Vec<String>,
+    /// Run after the files are rendered
+    #[serde(default)]
+    pub post: Vec<String>,
+}
+
+impl Placeholder {
+    /// Why `value` is rejected by the placeholder's type, `choices` or `regex`, if it is
+    ///
+    /// Mirrors the checks cargo-generate applies to provided values. A `regex` that
+    /// doesn't compile is left for cargo-generate to report.
+    pub fn check(&self, value: &str) -> Option<String> {
+        if self.kind == "bool" {
+            return value
+                .parse::<bool>()
+                .is_err()
+                .then(|| "expected true or false".to_string());
+        }
+        if !self.choices.is_empty() && !self.choices.iter().any(|choice| choice == value) {
+            return Some(format!("expected one of: {}", self.choices.join(", ")));
+        }
+        let regex = self.regex.as_deref()?;
+        match Regex::new(regex) {
+            Ok(compiled) if !compiled.is_match(value) => Some(format!("must match {}", regex)),
+            _ => None,
+        }
+    }
+}
+
+impl Hooks {
+    pub fn is_empty(&self) -> bool {
+        self.init.is_empty() && self.pre.is_empty() && self.post.is_empty()
+    }
+}
+
+/// README, placeholders and hooks of a template directory
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TemplateDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readme: Option<String>,
+    pub placeholders: Vec<Placeholder>,
+    pub hooks: Hooks,
+}
+
+#[derive(Deserialize)]
+struct GenerateFile {
+    #[serde(default)]
+    placeholders: BTreeMap<String, RawPlaceholder>,
+    #[serde(default)]
+    hooks: Hooks,
+}
+
+#[derive(Deserialize)]
+struct RawPlaceholder {
+    #[serde(rename = "type", default = "default_kind")]
+    kind: String,
+    prompt: Option<String>,
+    #[serde(default)]
+    choices: Vec<String>,
+    default: Option<toml::Value>,
+    regex: Option<String>,
+}
+
+fn default_kind() -> String {
+    "string".to_string()
+}
+
+impl TemplateDetails {
+    /// Read the details of the template rendered from `dir`
+    pub fn read(dir: &Path) -> Result<Self> {
+        let mut details = TemplateDetails {
+            readme: read_readme(dir)?,
+            ..Default::default()
+        };
+
+        let path = dir.join(GENERATE_FILE_NAME);
+        if !path.is_file() {
+            return Ok(details);
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let file: GenerateFile = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        details.placeholders = file
+            .placeholders
+            .into_iter()
+            .map(|(name, raw)| Placeholder {
+                name,
+                kind: raw.kind,
+                prompt: raw.prompt,
+                choices: raw.choices,
+                default: raw.default.map(|value| match value {
+                    toml::Value::String(s) => s,
+                    other => other.to_string(),
+                }),
+                regex: raw.regex,
+            })
+            .collect();
+        details.hooks = file.hooks;
+        Ok(details)
+    }
+}
+
+/// Contents of the first `README*` file in `dir`, preferring `README.md`
+fn read_readme(dir: &Path) -> Result<Option<String>> {
+    let mut candidates: Vec<_> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_lowercase())
+                    .is_some_and(|name| name.starts_with("readme"))
+        })
+        .collect();
+    candidates.sort_by_key(|path| {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        (!name.eq_ignore_ascii_case("README.md"), name.to_string())
+    });
+
+    match candidates.first() {
+        Some(path) => fs::read_to_string(path)
+            .map(Some)
+            .with_context(|| format!("Failed to read {}", path.display())),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_details() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("README.txt"), "plain").unwrap();
+        fs::write(dir.join("README.md"), "# {{project_name}}\n").unwrap();
+        fs::write(
+            dir.join(GENERATE_FILE_NAME),
+            r#"
+[template]
+ignore = ["target"]
+
+[placeholders.backend]
+type = "string"
+prompt = "Which proving backend?"
+choices = ["barretenberg", "plonky2"]
+default = "barretenberg"
+
+[placeholders.ci]
+type = "bool"
+prompt = "Add CI?"
+default = true
+
+[hooks]
+post = ["setup.rhai"]
+"#,
+        )
+        .unwrap();
+
+        let details = TemplateDetails::read(dir).unwrap();
+        assert_eq!(details.readme.as_deref(), Some("# {{project_name}}\n"));
+        assert_eq!(details.placeholders.len(), 2);
+        assert_eq!(details.placeholders[0].name, "backend");
+        assert_eq!(details.placeholders[0].choices.len(), 2);
+        assert_eq!(details.placeholders[1].kind, "bool");
+        assert_eq!(details.placeholders[1].default.as_deref(), Some("true"));
+        assert_eq!(details.hooks.post, vec!["setup.rhai".to_string()]);
+        assert!(details.hooks.pre.is_empty());
+    }
+
+    #[test]
+    fn test_placeholder_check() {
+        let placeholder = |kind: &str, choices: &[&str], regex: Option<&str>| Placeholder {
+            name: "backend".to_string(),
+            kind: kind.to_string(),
+            prompt: None,
+            choices: choices.iter().map(ToString::to_string).collect(),
+            default: None,
+            regex: regex.map(str::to_string),
+        };
+
+        let choices = placeholder("string", &["barretenberg", "plonky2"], None);
+        assert_eq!(choices.check("plonky2"), None);
+        assert_eq!(
+            choices.check("ultra").as_deref(),
+            Some("expected one of: barretenberg, plonky2")
+        );
+
+        let regex = placeholder("string", &[], Some("^[a-z]+$"));
+        assert_eq!(regex.check("abc"), None);
+        assert_eq!(regex.check("ABC").as_deref(), Some("must match ^[a-z]+$"));
+        assert_eq!(placeholder("string", &[], Some("(")).check("x"), None);
+
+        let flag = placeholder("bool", &[], None);
+        assert_eq!(flag.check("true"), None);
+        assert_eq!(flag.check("yes").as_deref(), Some("expected true or false"));
+    }
+
+    #[test]
+    fn test_read_details_without_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let details = TemplateDetails::read(temp_dir.path()).unwrap();
+        assert_eq!(details, TemplateDetails::default());
+        assert!(details.hooks.is_empty());
+
+        fs::write(temp_dir.path().join(GENERATE_FILE_NAME), "[placeholders").unwrap();
+        assert!(TemplateDetails::read(temp_dir.path()).is_err());
+    }
+}
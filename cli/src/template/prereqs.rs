@@ -0,0 +1,220 @@
+//! Template prerequisites
+//!
+//! Registry entries can list the tools a template needs in a `requires` table, mapping
+//! each tool to a semver requirement, or `*` when any version will do:
+//!
+//! ```toml
+//! [templates.noir-vite.requires]
+//! git = ">=2.30"
+//! mise = "*"
+//! ```
+//!
+//! `cza new` checks them before fetching the template. Tool versions are read from
+//! `<tool> --version`; partial versions such as `2.30` are padded (`2.30.0`) and
+//! pre-release or build suffixes are ignored when matching.
+
+use crate::utils;
+use anyhow::{anyhow, Result};
+use semver::{Version, VersionReq};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A prerequisite a tool installation doesn't satisfy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmetPrerequisite {
+    /// Tool name, as run on the command line
+    pub tool: String,
+    /// Version requirement from the registry (`*` for any version)
+    pub requirement: String,
+    /// Detected version, `None` when the tool isn't installed
+    pub found: Option<String>,
+}
+
+impl fmt::Display for UnmetPrerequisite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if is_any_version(&self.requirement) {
+            write!(f, "{}", self.tool)?;
+        } else {
+            write!(f, "{} {}", self.tool, self.requirement)?;
+        }
+        match &self.found {
+            Some(version) => write!(f, " (found {})", version),
+            None => write!(f, " (not found)"),
+        }
+    }
+}
+
+/// Format a `requires` table for display, e.g. `git >=2.30, mise`
+pub fn format_requirements(requires: &BTreeMap<String, String>) -> String {
+    requires
+        .iter()
+        .map(|(tool, requirement)| {
+            if is_any_version(requirement) {
+                tool.clone()
+            } else {
+                format!("{} {}", tool, requirement)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Check the tools installed on this machine against a `requires` table
+pub fn check_prerequisites(requires: &BTreeMap<String, String>) -> Result<Vec<UnmetPrerequisite>> {
+    check_with(requires, utils::detect_version)
+}
+
+/// Check a `requires` table using `detect` to find tool versions
+///
+/// `detect` follows [`utils::detect_version`]: `None` when the tool is missing and
+/// `Some(None)` when it runs but reports no version. Tools with an unknown version are
+/// assumed to satisfy their requirement.
+fn check_with(
+    requires: &BTreeMap<String, String>,
+    detect: impl Fn(&str) -> Option<Option<String>>,
+) -> Result<Vec<UnmetPrerequisite>> {
+    let mut unmet = Vec::new();
+    for (tool, requirement) in requires {
+        let version_req = if is_any_version(requirement) {
+            None
+        } else {
+            Some(VersionReq::parse(requirement).map_err(|e| {
+                anyhow!(
+                    "Invalid version requirement '{}' for {}: {}",
+                    requirement,
+                    tool,
+                    e
+                )
+            })?)
+        };
+
+        let found = match detect(tool) {
+            None => {
+                unmet.push(UnmetPrerequisite {
+                    tool: tool.clone(),
+                    requirement: requirement.clone(),
+                    found: None,
+                });
+                continue;
+            }
+            Some(found) => found,
+        };
+        let (Some(req), Some(found)) = (version_req, found) else {
+            continue;
+        };
+        if parse_lenient(&found).is_some_and(|version| !req.matches(&version)) {
+            unmet.push(UnmetPrerequisite {
+                tool: tool.clone(),
+                requirement: requirement.clone(),
+                found: Some(found),
+            });
+        }
+    }
+    Ok(unmet)
+}
+
+/// Whether a requirement accepts any installed version
+fn is_any_version(requirement: &str) -> bool {
+    matches!(requirement.trim(), "" | "*")
+}
+
+/// Parse a tool version, padding missing components and dropping pre-release and build
+/// suffixes
+pub(crate) fn parse_lenient(version: &str) -> Option<Version> {
+    let core = version
+        .trim()
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    Some(Version::new(major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requires(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(tool, req)| (tool.to_string(), req.to_string()))
+            .collect()
+    }
+
+    fn detect(tool: &str) -> Option<Option<String>> {
+        match tool {
+            "git" => Some(Some("2.39.5".to_string())),
+            "mise" => Some(Some("2024.5".to_string())),
+            "nargo" => Some(Some("0.30.0+af57".to_string())),
+            "hk" => Some(None),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        assert_eq!(parse_lenient("2.39.5"), Some(Version::new(2, 39, 5)));
+        assert_eq!(parse_lenient("v20.11"), Some(Version::new(20, 11, 0)));
+        assert_eq!(parse_lenient("1.2.0-beta"), Some(Version::new(1, 2, 0)));
+        assert_eq!(parse_lenient("0.30.0+af57"), Some(Version::new(0, 30, 0)));
+        assert_eq!(parse_lenient("unknown"), None);
+    }
+
+    #[test]
+    fn test_check_satisfied() {
+        let unmet = check_with(
+            &requires(&[
+                ("git", ">=2.30"),
+                ("mise", "*"),
+                ("nargo", "^0.30"),
+                ("hk", ">=1.0"),
+            ]),
+            detect,
+        )
+        .unwrap();
+        assert!(unmet.is_empty(), "{:?}", unmet);
+    }
+
+    #[test]
+    fn test_check_unmet() {
+        let unmet = check_with(
+            &requires(&[("git", ">=2.40"), ("scarb", "*"), ("mise", "")]),
+            detect,
+        )
+        .unwrap();
+        assert_eq!(
+            unmet,
+            vec![
+                UnmetPrerequisite {
+                    tool: "git".to_string(),
+                    requirement: ">=2.40".to_string(),
+                    found: Some("2.39.5".to_string()),
+                },
+                UnmetPrerequisite {
+                    tool: "scarb".to_string(),
+                    requirement: "*".to_string(),
+                    found: None,
+                },
+            ]
+        );
+        assert_eq!(unmet[0].to_string(), "git >=2.40 (found 2.39.5)");
+        assert_eq!(unmet[1].to_string(), "scarb (not found)");
+    }
+
+    #[test]
+    fn test_invalid_requirement() {
+        let err = check_with(&requires(&[("git", "newest")]), detect).unwrap_err();
+        assert!(err.to_string().contains("Invalid version requirement"));
+    }
+
+    #[test]
+    fn test_format_requirements() {
+        assert_eq!(
+            format_requirements(&requires(&[("mise", "*"), ("git", ">=2.30")])),
+            "git >=2.30, mise"
+        );
+    }
+}
@@ -0,0 +1,336 @@
+//! External template registries
+//!
+//! Registries are TOML files using the same format as the embedded `templates.toml`,
+//! optionally declaring their own `name`. They are located by URL or file path:
+//!
+//! - `https://` / `http://` registries are fetched over the network and cached under the
+//!   XDG cache directory (`~/.cache/cza/registries`) for `registry.cache_ttl` seconds
+//! - Anything else (including `file://` URLs) is read from the local filesystem
+//!
+//! When a remote registry cannot be fetched, a stale cached copy is used if available.
+//! With `--offline`, remote registries are only ever read from the cache.
+
+use super::TemplateRegistry;
+use crate::cache;
+use anyhow::{anyhow, Context, Result};
+use log::{debug, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+/// Timeout applied when fetching a remote registry
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A registry reference, as given in config or with `--registry [NAME=]LOCATION`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistrySpec {
+    /// Namespace for the registry's templates (falls back to the registry's own name)
+    pub name: Option<String>,
+    /// URL or file path of the registry file
+    pub location: String,
+}
+
+impl FromStr for RegistrySpec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // Only treat the prefix as a name if it can't be part of a URL or path
+        let (name, location) = match s.split_once('=') {
+            Some((name, location)) if !name.is_empty() && !name.contains(['/', ':', '\\', '.']) => {
+                (Some(name.to_string()), location)
+            }
+            _ => (None, s),
+        };
+
+        if location.is_empty() {
+            return Err(anyhow!("Registry location cannot be empty"));
+        }
+
+        Ok(Self {
+            name,
+            location: location.to_string(),
+        })
+    }
+}
+
+impl RegistrySpec {
+    /// Whether this registry has to be fetched over the network
+    pub fn is_remote(&self) -> bool {
+        self.location.starts_with("https://") || self.location.starts_with("http://")
+    }
+
+    /// Fallback namespace derived from the location's file stem
+    fn derived_name(&self) -> String {
+        let last_segment = self
+            .location
+            .trim_end_matches('/')
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or_default();
+        Path::new(last_segment)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .filter(|stem| !stem.is_empty())
+            .unwrap_or_else(|| "registry".to_string())
+    }
+}
+
+/// Directory where fetched remote registries are cached
+pub fn registry_cache_dir() -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .context("Could not find cache directory")?
+        .join("cza")
+        .join("registries");
+    Ok(cache_dir)
+}
+
+/// Load a registry and return it together with the namespace for its templates
+pub fn load_registry_spec(
+    spec: &RegistrySpec,
+    cache_ttl: u64,
+) -> Result<(String, TemplateRegistry)> {
+    let contents = if spec.is_remote() {
+        let cache_file = registry_cache_dir()?.join(cache_file_name(&spec.location));
+        if crate::context::offline() {
+            fs::read_to_string(&cache_file).map_err(|_| {
+                anyhow!(
+                    "Registry '{}' is not cached and --offline was given",
+                    spec.location
+                )
+            })?
+        } else {
+            load_remote(&spec.location, &cache_file, cache_ttl, fetch)?
+        }
+    } else {
+        let path = spec
+            .location
+            .strip_prefix(super::FILE_SCHEME)
+            .unwrap_or(&spec.location);
+        fs::read_to_string(path).with_context(|| format!("Failed to read registry '{}'", path))?
+    };
+
+    let registry: TemplateRegistry = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse registry '{}'", spec.location))?;
+    let name = spec
+        .name
+        .clone()
+        .or_else(|| registry.name.clone())
+        .unwrap_or_else(|| spec.derived_name());
+    // The namespace is the first segment of template keys
+    if name.is_empty() || name.contains('/') {
+        return Err(anyhow!(
+            "Invalid registry name '{}', names can't be empty or contain '/'",
+            name
+        ));
+    }
+
+    debug!(
+        "Loaded registry '{}' from {} with {} templates",
+        name,
+        spec.location,
+        registry.templates.len()
+    );
+    Ok((name, registry))
+}
+
+/// Read a remote registry from cache when fresh, otherwise fetch and refresh the cache
+///
+/// Falls back to a stale cached copy when fetching fails.
+fn load_remote(
+    url: &str,
+    cache_file: &Path,
+    cache_ttl: u64,
+    fetch: impl FnOnce(&str) -> Result<String>,
+) -> Result<String> {
+    if is_fresh(cache_file, cache_ttl) {
+        debug!("Using cached registry {} for {}", cache_file.display(), url);
+        return fs::read_to_string(cache_file).context("Failed to read cached registry");
+    }
+
+    match fetch(url) {
+        Ok(contents) => {
+            // Don't cache something that isn't a registry
+            toml::from_str::<TemplateRegistry>(&contents)
+                .with_context(|| format!("Failed to parse registry '{}'", url))?;
+            if let Some(parent) = cache_file.parent() {
+                fs::create_dir_all(parent).context("Failed to create registry cache directory")?;
+            }
+            fs::write(cache_file, &contents).context("Failed to write registry cache")?;
+            Ok(contents)
+        }
+        Err(e) if cache_file.exists() => {
+            warn!(
+                "Could not fetch registry {} ({}), using cached copy",
+                url, e
+            );
+            fs::read_to_string(cache_file).context("Failed to read cached registry")
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether a cache file exists and is younger than `ttl` seconds
+fn is_fresh(cache_file: &Path, ttl: u64) -> bool {
+    fs::metadata(cache_file)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < Duration::from_secs(ttl))
+}
+
+/// Fetch a registry file over HTTP(S)
+fn fetch(url: &str) -> Result<String> {
+    debug!("Fetching registry from {}", url);
+    let client = reqwest::blocking::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .user_agent(concat!("cza/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    client
+        .get(url)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .with_context(|| format!("Failed to fetch registry '{}'", url))
+}
+
+/// Cache file name for a registry location
+fn cache_file_name(location: &str) -> String {
+    format!("{}.toml", cache::cache_key(location))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const REGISTRY_TOML: &str = r#"
+name = "acme"
+
+[templates.noir-vite]
+name = "Acme Noir"
+description = "Acme's Noir starter"
+repository = "https://github.com/acme/starters"
+subfolder = "noir-vite"
+frameworks = ["noir"]
+"#;
+
+    #[test]
+    fn test_parse_registry_spec() {
+        let spec: RegistrySpec = "acme=https://acme.dev/registry.toml".parse().unwrap();
+        assert_eq!(spec.name, Some("acme".to_string()));
+        assert_eq!(spec.location, "https://acme.dev/registry.toml");
+        assert!(spec.is_remote());
+
+        let spec: RegistrySpec = "https://acme.dev/registry.toml?v=1".parse().unwrap();
+        assert_eq!(spec.name, None);
+        assert_eq!(spec.location, "https://acme.dev/registry.toml?v=1");
+
+        let spec: RegistrySpec = "./registries/acme.toml".parse().unwrap();
+        assert!(!spec.is_remote());
+        assert_eq!(spec.derived_name(), "acme");
+
+        assert!("acme=".parse::<RegistrySpec>().is_err());
+    }
+
+    #[test]
+    fn test_load_local_registry() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("registry.toml");
+        fs::write(&path, REGISTRY_TOML).unwrap();
+
+        let spec: RegistrySpec = path.display().to_string().parse().unwrap();
+        let (name, registry) = load_registry_spec(&spec, 0).unwrap();
+        assert_eq!(name, "acme");
+        assert!(registry.templates.contains_key("noir-vite"));
+
+        // An explicit name overrides the registry's own name
+        let spec: RegistrySpec = format!("team=file://{}", path.display()).parse().unwrap();
+        let (name, _) = load_registry_spec(&spec, 0).unwrap();
+        assert_eq!(name, "team");
+    }
+
+    #[test]
+    fn test_load_remote_caches_fetched_registry() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_file = temp_dir.path().join("registries").join("acme.toml");
+
+        let contents = load_remote("https://acme.dev/registry.toml", &cache_file, 3600, |_| {
+            Ok(REGISTRY_TOML.to_string())
+        })
+        .unwrap();
+        assert_eq!(contents, REGISTRY_TOML);
+        assert!(cache_file.exists());
+
+        // A fresh cache is used without fetching
+        let contents = load_remote("https://acme.dev/registry.toml", &cache_file, 3600, |_| {
+            panic!("should not fetch while the cache is fresh")
+        })
+        .unwrap();
+        assert_eq!(contents, REGISTRY_TOML);
+    }
+
+    #[test]
+    fn test_load_remote_falls_back_to_stale_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_file = temp_dir.path().join("acme.toml");
+        fs::write(&cache_file, REGISTRY_TOML).unwrap();
+
+        let contents = load_remote("https://acme.dev/registry.toml", &cache_file, 0, |_| {
+            Err(anyhow!("offline"))
+        })
+        .unwrap();
+        assert_eq!(contents, REGISTRY_TOML);
+    }
+
+    #[test]
+    fn test_load_remote_offline_without_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_file = temp_dir.path().join("acme.toml");
+
+        let result = load_remote("https://acme.dev/registry.toml", &cache_file, 0, |_| {
+            Err(anyhow!("offline"))
+        });
+        assert!(result.is_err());
+        assert!(!cache_file.exists());
+    }
+
+    #[test]
+    fn test_load_remote_rejects_invalid_registry() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_file = temp_dir.path().join("acme.toml");
+
+        let result = load_remote("https://acme.dev/registry.toml", &cache_file, 0, |_| {
+            Ok("<html>not a registry</html>".to_string())
+        });
+        assert!(result.is_err());
+        assert!(!cache_file.exists());
+    }
+
+    #[test]
+    fn test_cache_file_name() {
+        let name = cache_file_name("https://acme.dev/registry.toml");
+        assert!(name.starts_with("https___acme_dev_registry_toml-"));
+        assert!(name.ends_with(".toml"));
+        assert_eq!(name, cache_file_name("https://acme.dev/registry.toml"));
+        assert_ne!(
+            cache_file_name("https://acme.dev/a-b.toml"),
+            cache_file_name("https://acme.dev/a_b.toml")
+        );
+    }
+
+    #[test]
+    fn test_load_registry_rejects_invalid_names() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("registry.toml");
+        fs::write(&path, REGISTRY_TOML.replace("\"acme\"", "\"acme/team\"")).unwrap();
+
+        let spec: RegistrySpec = path.display().to_string().parse().unwrap();
+        let Err(err) = load_registry_spec(&spec, 0) else {
+            panic!("registry names can't contain '/'");
+        };
+        assert!(err.to_string().contains("acme/team"));
+    }
+}
@@ -0,0 +1,138 @@
+//! Template values
+//!
+//! Values for template placeholders come from, in increasing order of precedence:
+//!
+//! 1. `[template_values.<template>]` tables in the user configuration
+//! 2. A values file passed with `--values-file`
+//! 3. `--define KEY=VALUE` flags
+//!
+//! Values files are TOML, either flat or using cargo-generate's `[values]` table:
+//!
+//! ```toml
+//! [values]
+//! backend = "barretenberg"
+//! use_ci = true
+//! ```
+//!
+//! Every value is forwarded to cargo-generate as a `define`, after being checked against
+//! the type, `regex` or `choices` of the template's placeholder.
+
+use anyhow::{anyhow, Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Template values keyed by placeholder name
+pub type TemplateValues = BTreeMap<String, String>;
+
+/// Parse a `KEY=VALUE` define
+pub fn parse_define(s: &str) -> Result<(String, String)> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Invalid define '{}', expected KEY=VALUE", s))?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(anyhow!("Invalid define '{}', the key cannot be empty", s));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Convert a TOML table of values into template values
+///
+/// Strings, booleans and numbers are accepted; arrays and tables are not.
+pub fn table_values(table: &toml::Table) -> Result<TemplateValues> {
+    table
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                toml::Value::String(s) => s.clone(),
+                toml::Value::Boolean(b) => b.to_string(),
+                toml::Value::Integer(i) => i.to_string(),
+                toml::Value::Float(f) => f.to_string(),
+                _ => {
+                    return Err(anyhow!(
+                    "Unsupported value for '{}': only strings, booleans and numbers are allowed",
+                    key
+                ))
+                }
+            };
+            Ok((key.clone(), value))
+        })
+        .collect()
+}
+
+/// Read template values from a TOML file, flat or under a `[values]` table
+pub fn read_values_file(path: &Path) -> Result<TemplateValues> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read values file {}", path.display()))?;
+    let table: toml::Table = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse values file {}", path.display()))?;
+
+    let values = match table.get("values") {
+        Some(toml::Value::Table(values)) => values,
+        _ => &table,
+    };
+    table_values(values).with_context(|| format!("Invalid values file {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_define() {
+        assert_eq!(
+            parse_define("backend=barretenberg").unwrap(),
+            ("backend".to_string(), "barretenberg".to_string())
+        );
+        // Only the first '=' separates key and value
+        assert_eq!(
+            parse_define("flags=a=b").unwrap(),
+            ("flags".to_string(), "a=b".to_string())
+        );
+        assert_eq!(
+            parse_define("license=").unwrap(),
+            ("license".to_string(), String::new())
+        );
+        assert!(parse_define("backend").is_err());
+        assert!(parse_define("=value").is_err());
+    }
+
+    #[test]
+    fn test_table_values() {
+        let table: toml::Table = toml::from_str(
+            r#"
+backend = "barretenberg"
+use_ci = true
+port = 5173
+"#,
+        )
+        .unwrap();
+
+        let values = table_values(&table).unwrap();
+        assert_eq!(values["backend"], "barretenberg");
+        assert_eq!(values["use_ci"], "true");
+        assert_eq!(values["port"], "5173");
+
+        let table: toml::Table = toml::from_str("tags = [\"a\"]").unwrap();
+        assert!(table_values(&table).is_err());
+    }
+
+    #[test]
+    fn test_read_values_file() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let flat = temp_dir.path().join("flat.toml");
+        fs::write(&flat, "license = \"MIT\"\n").unwrap();
+        assert_eq!(read_values_file(&flat).unwrap()["license"], "MIT");
+
+        let nested = temp_dir.path().join("nested.toml");
+        fs::write(&nested, "[values]\nlicense = \"Apache-2.0\"\n").unwrap();
+        assert_eq!(read_values_file(&nested).unwrap()["license"], "Apache-2.0");
+
+        let missing = temp_dir.path().join("missing.toml");
+        let err = read_values_file(&missing).unwrap_err();
+        assert!(err.to_string().contains("Failed to read values file"));
+    }
+}
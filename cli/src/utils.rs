@@ -4,45 +4,89 @@
 //!
 //! - Running system commands with standardized logging and error handling
 //! - Retrieving git configuration values
+//! - Detecting installed tools and their versions
 //! - Running post-generation commands with user-friendly output
 //!
 //! These utilities are used throughout the CLI for consistent command execution and output formatting.
 
+use crate::error::CzaError;
 use crate::output;
+use crate::run_log::RunLog;
 use log::debug;
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
-use std::process::{Command, ExitStatus};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc;
+use std::thread;
 
 /// Run a command and handle common patterns (logging, error handling)
+///
+/// The lines the command prints on stdout and stderr are passed to `on_line` as they come.
+/// Its stdin is empty.
 pub fn run_command(
     command: &str,
     args: &[&str],
     working_dir: Option<&Path>,
     description: &str,
+    mut on_line: impl FnMut(&str),
 ) -> Result<ExitStatus, std::io::Error> {
     debug!("Running command: {} {:?}", command, args);
 
+    // With its output captured, a prompt would be invisible and the run would seem to
+    // hang, so the command can't read from the terminal and fails instead
     let mut cmd = Command::new(command);
-    cmd.args(args);
+    cmd.args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
 
     if let Some(dir) = working_dir {
         cmd.current_dir(dir);
     }
 
-    match cmd.status() {
-        Ok(status) => {
-            if status.success() {
-                debug!("{} completed successfully", description);
-            } else {
-                debug!("{} exited with status: {}", description, status);
-            }
-            Ok(status)
-        }
-        Err(e) => {
-            debug!("Failed to run {}: {}", command, e);
-            Err(e)
-        }
+    let mut child = cmd.spawn().inspect_err(|e| {
+        debug!("Failed to run {}: {}", command, e);
+    })?;
+
+    // Read both streams on their own threads so neither pipe fills up and blocks the command
+    let (sender, receiver) = mpsc::channel();
+    let stdout = child
+        .stdout
+        .take()
+        .map(|s| Box::new(s) as Box<dyn Read + Send>);
+    let stderr = child
+        .stderr
+        .take()
+        .map(|s| Box::new(s) as Box<dyn Read + Send>);
+    let readers: Vec<_> = [stdout, stderr]
+        .into_iter()
+        .flatten()
+        .map(|stream| {
+            let sender = sender.clone();
+            thread::spawn(move || {
+                for line in BufReader::new(stream).lines().map_while(Result::ok) {
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            })
+        })
+        .collect();
+    drop(sender);
+    for line in receiver {
+        on_line(&line);
+    }
+    for reader in readers {
+        let _ = reader.join();
     }
+
+    let status = child.wait()?;
+    if status.success() {
+        debug!("{} completed successfully", description);
+    } else {
+        debug!("{} exited with status: {}", description, status);
+    }
+    Ok(status)
 }
 
 /// Get a git config value
@@ -66,38 +110,79 @@ pub fn get_git_config(key: &str) -> Option<String> {
 }
 
 /// Run a post-generation command with standardized output
+///
+/// The full output goes to `log`. A failure is printed as a warning and returned as
+/// [`CzaError::PostGenFailed`].
 pub fn run_post_generation_command(
     command: &str,
     args: &[&str],
     working_dir: &Path,
     step_message: &str,
     success_message: &str,
-    failure_hint: Option<&str>,
-) -> Result<(), String> {
-    output::step(step_message);
+    log: &RunLog,
+) -> Result<(), CzaError> {
+    let mut progress = output::Progress::start(step_message);
+    let command_line = [command]
+        .iter()
+        .chain(args)
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ");
+    log.line(&format!(
+        "$ {} (in {})",
+        command_line,
+        working_dir.display()
+    ));
 
-    match run_command(command, args, Some(working_dir), step_message) {
+    let status = run_command(command, args, Some(working_dir), step_message, |line| {
+        log.output(line);
+        progress.line(line)
+    });
+    let reason = match status {
         Ok(status) if status.success() => {
-            output::success(success_message);
-            Ok(())
-        }
-        Ok(status) => {
-            let error_msg = format!("{} failed with status: {}", command, status);
-            output::warning(&error_msg);
-            if let Some(hint) = failure_hint {
-                output::info(hint);
-            }
-            Err(error_msg)
-        }
-        Err(e) => {
-            let error_msg = format!("Could not run {}: {}", command, e);
-            output::warning(&error_msg);
-            if let Some(hint) = failure_hint {
-                output::info(hint);
-            }
-            Err(error_msg)
+            log.line(&format!("{} succeeded", command_line));
+            progress.finish(success_message);
+            return Ok(());
         }
+        Ok(status) => status.to_string(),
+        Err(e) => e.to_string(),
+    };
+    log.line(&format!("{} failed: {}", command_line, reason));
+    let err = CzaError::PostGenFailed {
+        command: command_line,
+        reason,
+    };
+    // Later steps still run, the error is reported once they're done
+    progress.fail(&err.to_string());
+    Err(err)
+}
+
+/// Run `<command> --version`: `None` when the tool can't be run, `Some(None)` when it
+/// runs but its version can't be parsed
+pub fn detect_version(command: &str) -> Option<Option<String>> {
+    let output = Command::new(command).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return Some(None);
     }
+    Some(parse_version(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Extract the first version number from `--version` output
+///
+/// Handles the usual formats, e.g. `git version 2.43.0`, `v20.11.1`,
+/// `2024.5.1 linux-x64` or `nargo version = 0.30.0`.
+pub fn parse_version(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .map(|word| word.trim_start_matches('v').trim_end_matches([',', ';']))
+        .find(|word| {
+            word.contains('.')
+                && word.starts_with(|c: char| c.is_ascii_digit())
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+        })
+        .map(str::to_string)
 }
 
 #[cfg(test)]
@@ -114,22 +199,68 @@ mod tests {
     #[test]
     fn test_run_command_success() {
         // Use a simple command that should work on all platforms
-        let result = run_command("echo", &["test"], None, "echo test");
+        let mut lines = vec![];
+        let result = run_command("echo", &["test"], None, "echo test", |line| {
+            lines.push(line.to_string())
+        });
         assert!(result.is_ok());
         assert!(result.unwrap().success());
+        assert_eq!(lines, vec!["test".to_string()]);
     }
 
     #[test]
     fn test_run_command_with_working_dir() {
         let temp_dir = env::temp_dir();
-        let result = run_command("pwd", &[], Some(&temp_dir), "pwd in temp");
+        let result = run_command("pwd", &[], Some(&temp_dir), "pwd in temp", |_| {});
         // pwd might not exist on Windows, so we just check it doesn't panic
         let _ = result;
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_run_command_without_stdin() {
+        // `cat` would wait for input if it could read the terminal
+        let mut lines = vec![];
+        let status =
+            run_command("cat", &[], None, "cat", |line| lines.push(line.to_string())).unwrap();
+        assert!(status.success());
+        assert!(lines.is_empty());
+    }
+
     #[test]
     fn test_run_command_failure() {
-        let result = run_command("nonexistent_command", &[], None, "test");
+        let result = run_command("nonexistent_command", &[], None, "test", |_| {});
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            parse_version("git version 2.43.0\n"),
+            Some("2.43.0".to_string())
+        );
+        assert_eq!(parse_version("v20.11.1\n"), Some("20.11.1".to_string()));
+        assert_eq!(
+            parse_version("2024.5.1 linux-x64 (2024-05-10)"),
+            Some("2024.5.1".to_string())
+        );
+        assert_eq!(
+            parse_version("nargo version = 0.30.0\nnoirc version = 0.30.0+af57"),
+            Some("0.30.0".to_string())
+        );
+        assert_eq!(
+            parse_version("scarb 2.6.4 (c4c7c0bac 2024-03-19)"),
+            Some("2.6.4".to_string())
+        );
+        assert_eq!(
+            parse_version("hk 1.2.0-beta"),
+            Some("1.2.0-beta".to_string())
+        );
+        assert_eq!(parse_version("unknown"), None);
+    }
+
+    #[test]
+    fn test_detect_version_missing_tool() {
+        assert_eq!(detect_version("cza-definitely-not-installed"), None);
+    }
 }
@@ -50,11 +50,1233 @@ fn test_list_json_command() {
         .stdout(predicate::str::contains("\"noir-vite\""));
 }
 
+#[test]
+fn test_list_framework_filter() {
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.args(["list", "--json", "--framework", "cairo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"cairo-vite\""))
+        .stdout(predicate::str::contains("\"noir-vite\"").not());
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.args([
+        "list",
+        "--json",
+        "--framework",
+        "noir",
+        "--framework",
+        "cairo",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::starts_with("[]"));
+}
+
+#[test]
+fn test_search_command() {
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.args(["search", "starknet"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Templates matching 'starknet'"))
+        .stdout(predicate::str::contains("cairo-vite"))
+        .stdout(predicate::str::contains("noir-vite").not());
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.args(["search", "vite", "--json", "--framework", "noir"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"noir-vite\""))
+        .stdout(predicate::str::contains("\"cairo-vite\"").not());
+}
+
+#[test]
+fn test_info_command_local_template() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("starter");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(template_dir.join("README.md"), "# Starter readme\n").unwrap();
+    std::fs::write(
+        template_dir.join("cargo-generate.toml"),
+        r#"
+[placeholders.backend]
+type = "string"
+prompt = "Which proving backend?"
+default = "barretenberg"
+
+[hooks]
+post = ["setup.rhai"]
+"#,
+    )
+    .unwrap();
+    let registry = temp_dir.path().join("registry.toml");
+    std::fs::write(
+        &registry,
+        format!(
+            r#"
+[templates.starter]
+name = "Starter"
+description = "A local starter"
+repository = "file://{}"
+subfolder = "starter"
+frameworks = ["noir"]
+"#,
+            temp_dir.path().display()
+        ),
+    )
+    .unwrap();
+    let registry_arg = format!("local={}", registry.display());
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["info", "local/starter", "--registry", &registry_arg])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Starter (local/starter)"))
+        .stdout(predicate::str::contains("backend (string)"))
+        .stdout(predicate::str::contains("post: setup.rhai"))
+        .stdout(predicate::str::contains("# Starter readme"));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args([
+            "info",
+            "local/starter",
+            "--json",
+            "--registry",
+            &registry_arg,
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"fetched\": true"))
+        .stdout(predicate::str::contains("\"name\": \"backend\""))
+        .stdout(predicate::str::contains("\"action\": \"git_init\""))
+        // Same argv form as `cza new --dry-run --json`
+        .stdout(predicate::str::is_match(r#""command": \[\s*"git",\s*"init"\s*\]"#).unwrap());
+}
+
+#[test]
+fn test_info_command_unknown_template() {
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.args(["info", "nonexistent"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not found"));
+}
+
+#[test]
+fn test_unknown_template_suggestion() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["new", "my-app", "--template", "noir-vit", "--dry-run"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Template 'noir-vit' not found"))
+        .stdout(predicate::str::contains("Did you mean 'noir-vite'?"));
+}
+
+#[test]
+fn test_unknown_config_key_autocorrect() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "set", "user.autor", "Someone"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Did you mean 'user.author'?"));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "set", "development.autocorrect", "true"])
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "set", "user.autor", "Someone"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("using 'user.author'"));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "get", "user.author"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("user.author = Someone"));
+}
+
+#[test]
+fn test_config_schema_and_reset_key() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "schema"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"$schema\""))
+        .stdout(predicate::str::contains("\"cache_ttl\""));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "set", "registry.cache_ttl", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid number 'soon'"));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "set", "registry.cache_ttl", "60"])
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "reset", "registry.cache_ttl"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Reset registry.cache_ttl = 86400"));
+}
+
+#[test]
+fn test_config_layers_show_origin() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    let nested_dir = project_dir.join("nested");
+    std::fs::create_dir_all(&nested_dir).unwrap();
+    std::fs::write(
+        project_dir.join(".cza.toml"),
+        "[user]\nauthor = \"Project Author\"\nemail = \"team@example.com\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "set", "user.author", "Global Author"])
+        .current_dir(&nested_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("user.author is overridden"));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("CZA_USER__EMAIL", "env@example.com")
+        .args([
+            "-c",
+            "user.git_init=false",
+            "config",
+            "list",
+            "--show-origin",
+        ])
+        .current_dir(&nested_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("user.author = Project Author"))
+        .stdout(predicate::str::contains("project config"))
+        .stdout(predicate::str::contains(
+            "user.email = env@example.com  (environment variable CZA_USER__EMAIL)",
+        ))
+        .stdout(predicate::str::contains(
+            "user.git_init = false  (command line)",
+        ));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "get", "user.author"])
+        .current_dir(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("user.author = Global Author"));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("CZA_USER__GIT_INIT", "maybe")
+        .args(["config", "list"])
+        .current_dir(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("CZA_USER__GIT_INIT"))
+        .stderr(predicate::str::contains("Invalid boolean value 'maybe'"));
+}
+
+#[test]
+fn test_config_profiles() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_dir = temp_dir.path().join("cza");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[user]
+author = "Jane Doe"
+
+[profiles.work.user]
+author = "Jane Doe (ACME)"
+default_template = "noir-vite"
+
+[profiles.personal.post_generation]
+auto_install_deps = false
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "profile", "use", "work"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Using profile 'work'"));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "list", "--show-origin"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Profile: work"))
+        .stdout(predicate::str::contains(
+            "user.author = Jane Doe (ACME)  (profile work)",
+        ));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("CZA_PROFILE", "personal")
+        .args(["config", "get", "user.author"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("user.author = Jane Doe"))
+        .stdout(predicate::str::contains("ACME").not());
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["--profile", "personal", "config", "profile", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "* personal  (active, from command line)",
+        ))
+        .stdout(predicate::str::contains("  work"));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "profile", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "user.default_template = noir-vite",
+        ));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["--profile", "wrok", "config", "list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Profile 'wrok' not found"))
+        .stdout(predicate::str::contains("Did you mean 'work'?"));
+}
+
+#[test]
+fn test_list_includes_user_templates() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_dir = temp_dir.path().join("cza");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[templates.my-starter]
+name = "My Starter"
+description = "A custom starter"
+repository = "https://github.com/me/starters"
+subfolder = "my-starter"
+frameworks = ["noir"]
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["list", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"my-starter\""))
+        .stdout(predicate::str::contains("\"source\": \"user\""))
+        .stdout(predicate::str::contains("\"source\": \"builtin\""));
+}
+
+#[test]
+fn test_new_from_local_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("my-template");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(
+        template_dir.join("README.md"),
+        "# {{project_name}} by {{author}}\n",
+    )
+    .unwrap();
+
+    let workspace = temp_dir.path().join("workspace");
+    std::fs::create_dir_all(&workspace).unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.current_dir(&workspace)
+        .env("XDG_CONFIG_HOME", temp_dir.path())
+        .args([
+            "config",
+            "set",
+            "post_generation.auto_install_deps",
+            "false",
+        ])
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.current_dir(&workspace)
+        .env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+        .env("USER", "tester")
+        .args(["-c", "post_generation.auto_setup_hooks=false"])
+        .args(["new", "my-app", "--author", "Test Author", "--path"])
+        .arg(&template_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Project created successfully"));
+
+    let readme = std::fs::read_to_string(workspace.join("my-app").join("README.md")).unwrap();
+    assert_eq!(readme, "# my-app by Test Author\n");
+
+    let lock = std::fs::read_to_string(workspace.join("my-app").join(".cza.lock")).unwrap();
+    let lock: toml::Table = toml::from_str(&lock).unwrap();
+    assert_eq!(lock["template"]["key"].as_str(), Some("my-template"));
+    assert_eq!(lock["defines"]["project_name"].as_str(), Some("my-app"));
+    assert_eq!(lock["defines"]["author"].as_str(), Some("Test Author"));
+    assert_eq!(
+        lock["cza_version"].as_str(),
+        Some(env!("CARGO_PKG_VERSION"))
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_post_generation_progress_without_terminal() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("my-template");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(template_dir.join("README.md"), "# {{project_name}}\n").unwrap();
+    let path = failing_mise_path(temp_dir.path());
+
+    let workspace = temp_dir.path().join("workspace");
+    std::fs::create_dir_all(&workspace).unwrap();
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.current_dir(&workspace)
+        .env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("USER", "tester")
+        .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+        .env("PATH", path)
+        .args(["-c", "post_generation.auto_setup_hooks=false"])
+        .args(["new", "my-app", "--author", "Test Author", "--path"])
+        .arg(&template_dir)
+        .assert()
+        // The project is kept, but the run fails with the failed step
+        .code(18)
+        // Plain lines instead of a spinner, with the command output and elapsed time
+        .stdout(predicate::str::contains("mise config trusted!"))
+        .stdout(predicate::str::contains(
+            "Installing dependencies with mise...",
+        ))
+        .stdout(predicate::str::contains("    installing noir"))
+        .stdout(predicate::str::contains("    download failed"))
+        .stdout(
+            predicate::str::is_match(r"mise install failed: exit status: 3 \(\d+\.\ds\)").unwrap(),
+        )
+        .stdout(predicate::str::is_match(r"Project created successfully! \(\d+\.\ds\)").unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_new_writes_run_log() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("my-template");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(template_dir.join("README.md"), "# {{project_name}}\n").unwrap();
+    let path = failing_mise_path(temp_dir.path());
+
+    let workspace = temp_dir.path().join("workspace");
+    std::fs::create_dir_all(&workspace).unwrap();
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.current_dir(&workspace)
+        .env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+        .env("USER", "tester")
+        .env("PATH", path)
+        .args(["-c", "post_generation.auto_setup_hooks=false"])
+        .args(["new", "my-app", "--author", "Test Author", "--path"])
+        .arg(&template_dir)
+        .assert()
+        .code(18)
+        .stdout(predicate::str::contains("Details of this run are in "));
+
+    let logs: Vec<_> = std::fs::read_dir(temp_dir.path().join("logs"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(logs.len(), 1);
+    let contents = std::fs::read_to_string(&logs[0]).unwrap();
+    assert!(contents.contains("Resolved configuration:"));
+    assert!(contents.contains("    auto_setup_hooks = false\n"));
+    assert!(contents.contains("cargo-generate arguments:"));
+    assert!(contents.contains("\"author=Test Author\""));
+    assert!(contents.contains("$ mise trust"));
+    assert!(contents.contains("$ mise install"));
+    assert!(contents.contains("    | installing noir\n"));
+    assert!(contents.contains("    | download failed\n"));
+    assert!(contents.contains("mise install failed: exit status: 3"));
+}
+
+/// `PATH` with a failing `mise` standing in for the real one, printing to stdout and stderr
+#[cfg(unix)]
+fn failing_mise_path(dir: &std::path::Path) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    let bin = dir.join("bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    let mise = bin.join("mise");
+    std::fs::write(
+        &mise,
+        "#!/bin/sh\n[ \"$1\" = trust ] && exit 0\necho 'installing noir'\necho 'download failed' >&2\nexit 3\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&mise, std::fs::Permissions::from_mode(0o755)).unwrap();
+    format!("{}:{}", bin.display(), std::env::var("PATH").unwrap())
+}
+
+#[test]
+fn test_new_dry_run_renders_file_tree() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("my-template");
+    std::fs::create_dir_all(template_dir.join("circuits")).unwrap();
+    std::fs::write(template_dir.join("README.md"), "# {{project_name}}\n").unwrap();
+    std::fs::write(
+        template_dir.join("circuits").join("main.nr"),
+        "fn main() {}\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("USER", "tester")
+        .args([
+            "new",
+            "my-app",
+            "--no-git",
+            "--dry-run",
+            "--author",
+            "Test Author",
+            "--path",
+        ])
+        .arg(&template_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("circuits/"))
+        .stdout(predicate::str::contains("main.nr (13 B)"))
+        .stdout(predicate::str::contains("author = Test Author"))
+        .stdout(predicate::str::contains(
+            "[skip] git init (--no-git flag provided)",
+        ))
+        .stdout(predicate::str::contains("[run] mise install"));
+
+    assert!(!temp_dir.path().join("my-app").exists());
+}
+
+#[test]
+fn test_new_dry_run_json_plan() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("my-template");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(template_dir.join("README.md"), "# {{project_name}}\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    let output = cmd
+        .current_dir(temp_dir.path())
+        .env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("USER", "tester")
+        .args([
+            "new",
+            "my-app",
+            "--dry-run",
+            "--json",
+            "--author",
+            "Test Author",
+            "--path",
+        ])
+        .arg(&template_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(plan["template"], "my-template");
+    assert_eq!(plan["defines"]["author"], "Test Author");
+    assert_eq!(plan["defines"]["project_name"], "my-app");
+    assert!(plan["destination"].as_str().unwrap().ends_with("my-app"));
+    assert_eq!(plan["files"][0]["path"], "README.md");
+    assert_eq!(plan["files"][0]["size"], 9);
+    assert_eq!(plan["post_generation"][0]["action"], "git_init");
+    assert_eq!(plan["post_generation"][0]["command"][0], "git");
+    assert_eq!(plan["post_generation"][0]["enabled"], true);
+    assert!(!temp_dir.path().join("my-app").exists());
+}
+
+#[test]
+fn test_new_json_requires_dry_run() {
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.args(["new", "my-app", "--json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--dry-run"));
+}
+
+#[test]
+fn test_new_without_arguments_is_not_interactive_without_tty() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .arg("new")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("required"));
+
+    // A missing name stays a usage error, reported before anything else runs
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+        .args(["new", "--template", "noir-vite"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("<PROJECT_NAME>"))
+        .stdout(predicate::str::contains("Usage: cza new"));
+    assert!(!temp_dir.path().join("logs").exists());
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+        .args(["new", "my-app"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No template specified"));
+}
+
+#[test]
+fn test_new_with_template_values() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("my-template");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(
+        template_dir.join("cargo-generate.toml"),
+        r#"
+[placeholders.backend]
+type = "string"
+prompt = "Proving backend?"
+choices = ["barretenberg", "plonky2"]
+
+[placeholders.license]
+type = "string"
+prompt = "License?"
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        template_dir.join("README.md"),
+        "# {{project_name}}\n{{backend}} {{license}} {{package_manager}}\n",
+    )
+    .unwrap();
+    let config_dir = temp_dir.path().join("cza");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[post_generation]
+auto_install_deps = false
+
+[template_values.my-template]
+license = "MIT"
+package_manager = "npm"
+"#,
+    )
+    .unwrap();
+    let values_file = temp_dir.path().join("vars.toml");
+    std::fs::write(&values_file, "[values]\npackage_manager = \"bun\"\n").unwrap();
+
+    let cza = || {
+        let mut cmd = Command::cargo_bin("cza").unwrap();
+        cmd.current_dir(temp_dir.path())
+            .env("XDG_CONFIG_HOME", temp_dir.path())
+            .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+            .env("USER", "tester");
+        cmd
+    };
+
+    cza()
+        .args([
+            "new",
+            "bad-app",
+            "--no-git",
+            "--define",
+            "backend=ultra",
+            "--path",
+        ])
+        .arg(&template_dir)
+        .assert()
+        .code(17)
+        .stderr(predicate::str::contains(
+            "Invalid value \"ultra\" for template placeholder 'backend'",
+        ))
+        .stderr(predicate::str::contains("expected one of"));
+
+    cza()
+        .args([
+            "new",
+            "my-app",
+            "--no-git",
+            "-d",
+            "backend=plonky2",
+            "--values-file",
+        ])
+        .arg(&values_file)
+        .arg("--path")
+        .arg(&template_dir)
+        .assert()
+        .success();
+
+    let readme = std::fs::read_to_string(temp_dir.path().join("my-app").join("README.md")).unwrap();
+    assert_eq!(readme, "# my-app\nplonky2 MIT bun\n");
+
+    let lock = std::fs::read_to_string(temp_dir.path().join("my-app").join(".cza.lock")).unwrap();
+    assert!(lock.contains("backend = \"plonky2\""));
+}
+
+#[test]
+fn test_new_path_conflicts_with_template() {
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.args(["new", "my-app", "--template", "noir-vite", "--path", "."])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_list_with_external_registry() {
+    let temp_dir = TempDir::new().unwrap();
+    let registry = temp_dir.path().join("registry.toml");
+    std::fs::write(
+        &registry,
+        r#"
+[templates.noir-vite]
+name = "Acme Noir"
+description = "Acme's Noir starter"
+repository = "https://github.com/acme/starters"
+subfolder = "noir-vite"
+frameworks = ["noir"]
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("XDG_CACHE_HOME", temp_dir.path())
+        .args(["list", "--json", "--registry"])
+        .arg(format!("acme={}", registry.display()))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"acme/noir-vite\""))
+        .stdout(predicate::str::contains("\"noir-vite\""))
+        .stdout(predicate::str::contains("\"source\": \"registry\""));
+}
+
+/// Run git in `dir`, returning whether it succeeded
+fn git(dir: &std::path::Path, args: &[&str]) -> bool {
+    Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+#[test]
+fn test_upgrade_merges_template_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("template");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    if !git(&template_dir, &["init", "-q"]) {
+        return;
+    }
+    std::fs::write(
+        template_dir.join("README.md"),
+        "# {{project_name}}\n\nIntro\n\nUsage\n",
+    )
+    .unwrap();
+    git(&template_dir, &["add", "."]);
+    git(&template_dir, &["commit", "-qm", "v1"]);
+    git(&template_dir, &["tag", "v1"]);
+    std::fs::write(
+        template_dir.join("README.md"),
+        "# {{project_name}}\n\nIntro\n\nBetter usage\n",
+    )
+    .unwrap();
+    std::fs::write(template_dir.join("NEW.md"), "new file\n").unwrap();
+    git(&template_dir, &["add", "."]);
+    git(&template_dir, &["commit", "-qm", "v2"]);
+
+    let workspace = temp_dir.path().join("workspace");
+    std::fs::create_dir_all(&workspace).unwrap();
+    let cza = |dir: &std::path::Path| {
+        let mut cmd = Command::cargo_bin("cza").unwrap();
+        cmd.current_dir(dir)
+            .env("XDG_CONFIG_HOME", temp_dir.path())
+            .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+            .env("USER", "tester");
+        cmd
+    };
+
+    cza(&workspace)
+        .args([
+            "config",
+            "set",
+            "post_generation.auto_install_deps",
+            "false",
+        ])
+        .assert()
+        .success();
+    cza(&workspace)
+        .args(["new", "my-app", "--no-git", "--revision", "v1", "--path"])
+        .arg(&template_dir)
+        .assert()
+        .success();
+
+    // Edit the project locally, in a different place than the template change
+    let project = workspace.join("my-app");
+    std::fs::write(
+        project.join("README.md"),
+        "# my-app\n\nOur intro\n\nUsage\n",
+    )
+    .unwrap();
+
+    cza(&project)
+        .args(["upgrade", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+Better usage"))
+        .stdout(predicate::str::contains("+++ b/NEW.md"));
+    assert!(!project.join("NEW.md").exists());
+
+    cza(&project).arg("upgrade").assert().success();
+    assert_eq!(
+        std::fs::read_to_string(project.join("README.md")).unwrap(),
+        "# my-app\n\nOur intro\n\nBetter usage\n"
+    );
+    assert!(project.join("NEW.md").exists());
+    let lock = std::fs::read_to_string(project.join(".cza.lock")).unwrap();
+    assert!(lock.contains("generated_at"));
+    assert!(lock.contains("upgraded_at"));
+
+    cza(&project)
+        .arg("upgrade")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("already up to date"));
+}
+
+#[test]
+fn test_cache_warm_list_and_clean() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("templates");
+    std::fs::create_dir_all(template_dir.join("starter")).unwrap();
+    if !git(&template_dir, &["init", "-q"]) {
+        return;
+    }
+    std::fs::write(
+        template_dir.join("starter").join("README.md"),
+        "# starter\n",
+    )
+    .unwrap();
+    git(&template_dir, &["add", "."]);
+    git(&template_dir, &["commit", "-qm", "init"]);
+
+    // A plain path (not file://) is treated like a remote repository and cloned
+    let registry = temp_dir.path().join("registry.toml");
+    std::fs::write(
+        &registry,
+        format!(
+            r#"
+[templates.starter]
+name = "Starter"
+description = "Cached starter"
+repository = "{}"
+subfolder = "starter"
+frameworks = []
+"#,
+            template_dir.display()
+        ),
+    )
+    .unwrap();
+
+    let cza = || {
+        let mut cmd = Command::cargo_bin("cza").unwrap();
+        cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+            .env("XDG_CACHE_HOME", temp_dir.path().join("cache"));
+        cmd
+    };
+
+    cza()
+        .args(["cache", "warm", "local/starter", "--registry"])
+        .arg(format!("local={}", registry.display()))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cached local/starter"));
+
+    cza()
+        .args(["cache", "list", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(template_dir.display().to_string()));
+
+    cza()
+        .args(["--offline", "cache", "warm"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--offline"));
+
+    cza().args(["cache", "clean"]).assert().success();
+    cza()
+        .args(["cache", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("empty"));
+}
+
+#[test]
+fn test_new_offline_requires_cached_template() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+        .env("XDG_CACHE_HOME", temp_dir.path())
+        .env("USER", "tester")
+        .args([
+            "new",
+            "my-app",
+            "--template",
+            "noir-vite",
+            "--offline",
+            "--no-git",
+            "--skip-prereq-check",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Template 'Noir + Vite + TanStack'",
+        ))
+        .stderr(predicate::str::contains("at revision latest is not cached"));
+    assert!(!temp_dir.path().join("my-app").exists());
+}
+
+#[test]
+fn test_new_checks_template_prerequisites() {
+    let temp_dir = TempDir::new().unwrap();
+    let registry = temp_dir.path().join("registry.toml");
+    std::fs::write(
+        &registry,
+        r#"
+[templates.needs-tools]
+name = "Needs tools"
+description = "Template with prerequisites"
+repository = "https://github.com/acme/starters"
+subfolder = "needs-tools"
+frameworks = []
+
+[templates.needs-tools.requires]
+git = ">=1.0"
+cza-missing-tool = "*"
+"#,
+    )
+    .unwrap();
+
+    let cza = || {
+        let mut cmd = Command::cargo_bin("cza").unwrap();
+        cmd.current_dir(temp_dir.path())
+            .env("XDG_CONFIG_HOME", temp_dir.path())
+            .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+            .env("XDG_CACHE_HOME", temp_dir.path())
+            .env("USER", "tester");
+        cmd
+    };
+
+    cza()
+        .args(["list", "--detailed", "--registry"])
+        .arg(format!("acme={}", registry.display()))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Requires: cza-missing-tool, git >=1.0",
+        ));
+
+    // The check fails before anything is fetched
+    cza()
+        .args([
+            "new",
+            "my-app",
+            "-t",
+            "acme/needs-tools",
+            "--offline",
+            "--registry",
+        ])
+        .arg(format!("acme={}", registry.display()))
+        .assert()
+        .code(22)
+        .stderr(predicate::str::contains("cza-missing-tool (not found)"))
+        .stdout(predicate::str::contains("--skip-prereq-check"));
+
+    // Previews don't stop at unmet prerequisites and move on to fetching the template
+    cza()
+        .args([
+            "new",
+            "my-app",
+            "-t",
+            "acme/needs-tools",
+            "--offline",
+            "--dry-run",
+        ])
+        .args(["--registry"])
+        .arg(format!("acme={}", registry.display()))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not cached"));
+
+    // Skipping the check moves on to fetching the template
+    cza()
+        .args(["new", "my-app", "-t", "acme/needs-tools", "--offline"])
+        .args(["--skip-prereq-check", "--registry"])
+        .arg(format!("acme={}", registry.display()))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not cached"));
+}
+
+#[test]
+fn test_incompatible_templates_are_flagged_and_refused() {
+    let temp_dir = TempDir::new().unwrap();
+    let registry = temp_dir.path().join("registry.toml");
+    std::fs::write(
+        &registry,
+        r#"
+[templates.future]
+name = "Future"
+description = "Needs a newer cza"
+repository = "https://github.com/acme/starters"
+subfolder = "future"
+frameworks = []
+min_cza_version = "999.0"
+"#,
+    )
+    .unwrap();
+
+    let cza = || {
+        let mut cmd = Command::cargo_bin("cza").unwrap();
+        cmd.current_dir(temp_dir.path())
+            .env("XDG_CONFIG_HOME", temp_dir.path())
+            .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+            .env("XDG_CACHE_HOME", temp_dir.path())
+            .env("USER", "tester");
+        cmd
+    };
+    let registry_arg = format!("acme={}", registry.display());
+
+    cza()
+        .args(["list", "--registry", &registry_arg])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("requires cza >= 999.0"));
+    cza()
+        .args(["list", "--json", "--registry", &registry_arg])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"min_cza_version\": \"999.0\""))
+        .stdout(predicate::str::contains("\"compatible\": false"));
+    cza()
+        .args([
+            "new",
+            "my-app",
+            "-t",
+            "acme/future",
+            "--registry",
+            &registry_arg,
+        ])
+        .assert()
+        .code(21)
+        .stderr(predicate::str::contains(
+            "Template 'acme/future' requires cza >= 999.0",
+        ))
+        .stdout(predicate::str::contains("cza update"));
+}
+
+#[test]
+fn test_template_manifest_limits_cza_version() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("template");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(template_dir.join("README.md"), "# {{project_name}}\n").unwrap();
+    std::fs::write(template_dir.join("cza.toml"), "max_cza_version = \"0.1\"\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+        .env("USER", "tester")
+        .args(["new", "my-app", "--path"])
+        .arg(&template_dir)
+        .assert()
+        .code(21)
+        .stderr(predicate::str::contains("supports cza up to 0.1"))
+        .stdout(predicate::str::contains("--revision"));
+    assert!(!temp_dir.path().join("my-app").exists());
+
+    // Previews report the check instead of failing
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["new", "my-app", "--dry-run", "--path"])
+        .arg(&template_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("README.md"))
+        .stdout(predicate::str::contains("supports cza up to 0.1"));
+
+    // A manifest that can't be read fails the check instead of skipping it
+    std::fs::write(template_dir.join("cza.toml"), "max_cza_version = [").unwrap();
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+        .env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["new", "my-app", "--path"])
+        .arg(&template_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Could not check the cza versions"));
+    assert!(!temp_dir.path().join("my-app").exists());
+}
+
+#[test]
+fn test_diff_shows_local_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("template");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(template_dir.join("README.md"), "# {{project_name}}\n").unwrap();
+    std::fs::write(template_dir.join("main.nr"), "fn main() {}\n").unwrap();
+
+    let workspace = temp_dir.path().join("workspace");
+    std::fs::create_dir_all(&workspace).unwrap();
+    let cza = |dir: &std::path::Path| {
+        let mut cmd = Command::cargo_bin("cza").unwrap();
+        cmd.current_dir(dir)
+            .env("XDG_CONFIG_HOME", temp_dir.path())
+            .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+            .env("USER", "tester");
+        cmd
+    };
+
+    cza(&workspace)
+        .args([
+            "config",
+            "set",
+            "post_generation.auto_install_deps",
+            "false",
+        ])
+        .assert()
+        .success();
+    cza(&workspace)
+        .args(["new", "my-app", "--no-git", "--path"])
+        .arg(&template_dir)
+        .assert()
+        .success();
+
+    let project = workspace.join("my-app");
+    cza(&project)
+        .arg("diff")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Project matches the template"));
+
+    std::fs::write(project.join("README.md"), "# my-app\n\nNotes\n").unwrap();
+    std::fs::write(project.join("extra.txt"), "extra\n").unwrap();
+
+    cza(&project)
+        .args(["diff", "--name-only"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("M README.md"))
+        .stdout(predicate::str::contains("A extra.txt"));
+
+    cza(&project)
+        .args(["diff", "--json", "--ignore", "extra.txt"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"status\": \"modified\""))
+        .stdout(predicate::str::contains("+Notes"))
+        .stdout(predicate::str::contains("extra.txt").not());
+}
+
+#[test]
+fn test_doctor_reports_missing_tools() {
+    let temp_dir = TempDir::new().unwrap();
+    let empty_path = temp_dir.path().join("bin");
+    std::fs::create_dir_all(&empty_path).unwrap();
+
+    // Without any tools on PATH, git (always required) fails the run
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("XDG_CACHE_HOME", temp_dir.path())
+        .env("PATH", &empty_path)
+        .args(["doctor", "--json"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("\"name\": \"git\""))
+        .stdout(predicate::str::contains("\"status\": \"fail\""))
+        .stdout(predicate::str::contains("https://git-scm.com"))
+        .stderr(predicate::str::contains("required check"));
+}
+
+#[test]
+fn test_doctor_reports_invalid_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_dir = temp_dir.path().join("cza");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(config_dir.join("config.toml"), "[user\nauthor = ").unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("XDG_CACHE_HOME", temp_dir.path())
+        .arg("doctor")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("config file"))
+        .stdout(predicate::str::contains("Failed to parse config file"));
+}
+
 #[test]
 fn test_invalid_template() {
     let temp_dir = TempDir::new().unwrap();
     let mut cmd = Command::cargo_bin("cza").unwrap();
     cmd.current_dir(&temp_dir)
+        .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
         .args(["new", "test-project", "--template", "nonexistent-template"])
         .assert()
         .failure()
@@ -66,9 +1288,167 @@ fn test_invalid_project_name() {
     let temp_dir = TempDir::new().unwrap();
     let mut cmd = Command::cargo_bin("cza").unwrap();
     cmd.current_dir(&temp_dir)
+        .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
         .args(["new", "invalid name", "--template", "noir-vite"])
+        .arg("--skip-prereq-check")
         .assert()
-        .failure();
+        .code(13)
+        .stderr(predicate::str::contains("Project name can only contain"));
+}
+
+#[test]
+fn test_errors_have_codes_and_json_reports() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["info", "noir-vit", "--json"])
+        .assert()
+        .code(10)
+        .stderr(predicate::str::contains("\"code\": \"template_not_found\""))
+        .stderr(predicate::str::contains("\"exit_code\": 10"))
+        .stderr(predicate::str::contains(
+            "\"suggestions\": [\n      \"noir-vite\"",
+        ));
+
+    std::fs::create_dir(temp_dir.path().join("my-app")).unwrap();
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("CZA_LOG_DIR", temp_dir.path().join("logs"))
+        .args(["new", "my-app", "--template", "noir-vite"])
+        .arg("--skip-prereq-check")
+        .assert()
+        .code(14)
+        .stderr(predicate::str::contains(
+            "Directory 'my-app' already exists",
+        ))
+        .stdout(predicate::str::contains("Choose a different project name"));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["-c", "user.git_init=maybe", "config", "list"])
+        .assert()
+        .code(20)
+        .stderr(predicate::str::contains(
+            "Invalid value for user.git_init from command line",
+        ));
+}
+
+#[test]
+fn test_output_json_events() {
+    let temp_dir = TempDir::new().unwrap();
+
+    /// Parse every stdout line of `cza --output json <args>` as an event
+    fn events(temp_dir: &TempDir, args: &[&str]) -> (Option<i32>, Vec<serde_json::Value>) {
+        let output = Command::cargo_bin("cza")
+            .unwrap()
+            .env("XDG_CONFIG_HOME", temp_dir.path())
+            .args(["--output", "json"])
+            .args(args)
+            .output()
+            .unwrap();
+        let events = String::from_utf8(output.stdout)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        for event in &events {
+            assert!(event["timestamp"].is_string(), "{}", event);
+            assert!(event["event"].is_string(), "{}", event);
+        }
+        (output.status.code(), events)
+    }
+
+    let (code, set) = events(&temp_dir, &["config", "set", "user.author", "Jane"]);
+    assert_eq!(code, Some(0));
+    assert_eq!(set[0]["event"], "step_finished");
+    assert_eq!(set[0]["message"], "Set user.author = Jane");
+    let result = set.last().unwrap();
+    assert_eq!(result["event"], "result");
+    assert_eq!(result["result"]["value"], "Jane");
+
+    let (_, list) = events(&temp_dir, &["config", "list"]);
+    let values = &list.last().unwrap()["result"]["values"];
+    assert_eq!(values[0]["key"], "user.author");
+    assert!(values[0]["origin"]
+        .as_str()
+        .unwrap()
+        .starts_with("global config"));
+    assert!(values[1]["value"].is_null());
+
+    // Commands with --json report their data as the result
+    let (_, list) = events(&temp_dir, &["list"]);
+    assert_eq!(list.len(), 1);
+    assert!(list[0]["result"].as_array().unwrap().len() >= 2);
+
+    let (code, info) = events(&temp_dir, &["info", "noir-vit"]);
+    assert_eq!(code, Some(10));
+    assert_eq!(info[0]["event"], "error");
+    assert_eq!(info[0]["code"], "template_not_found");
+    assert_eq!(info[0]["suggestions"][0], "noir-vite");
+}
+
+#[test]
+fn test_verbosity_and_color_flags() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["-q", "config", "set", "user.author", "Jane"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    // Quiet `config get` prints the bare value
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["config", "get", "user.author", "--quiet"])
+        .assert()
+        .success()
+        .stdout("Jane\n");
+
+    // Errors keep their suggestions and hint
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .args(["-q", "config", "get", "user.autor"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Did you mean 'user.author'?"))
+        .stdout(predicate::str::contains("cza config list"));
+
+    // --color always wins over the config and NO_COLOR, even when piped
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("NO_COLOR", "1")
+        .args(["--color", "always", "-c", "development.color=false"])
+        .args(["config", "get", "user.author"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\u{1b}["));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .env("CLICOLOR_FORCE", "1")
+        .env("NO_COLOR", "1")
+        .args(["config", "get", "user.author"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\u{1b}[").not());
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path())
+        .env_remove("RUST_LOG")
+        .args(["-v", "config", "get", "user.author"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("DEBUG"));
+
+    let mut cmd = Command::cargo_bin("cza").unwrap();
+    cmd.args(["-v", "-q", "list"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
 }
 
 #[test]
@@ -76,7 +1456,7 @@ fn test_missing_arguments() {
     let mut cmd = Command::cargo_bin("cza").unwrap();
     cmd.arg("new")
         .assert()
-        .failure()
+        .code(2)
         .stderr(predicate::str::contains("required"));
 }
 